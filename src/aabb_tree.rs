@@ -0,0 +1,498 @@
+use crate::{clip::liang_barsky, Ray, RayHit, Raycast, Rect};
+
+const NONE: usize = usize::MAX;
+
+/// Identifies an item in an `AabbTree`. Handles are reused once their item is removed.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct AabbTreeHandle(usize);
+
+#[derive(Clone, Debug)]
+enum Kind<V, T> {
+    Branch([usize; 2]),
+    Leaf { bounds: Rect<T>, value: V },
+    Free,
+}
+
+#[derive(Clone, Debug)]
+struct Node<V, T> {
+    /// For leaves, this is the item's bounds outset by the tree's margin.
+    bounds: Rect<T>,
+    parent: usize,
+    /// How many levels there are below this node, which is 0 for leaves.
+    height: usize,
+    kind: Kind<V, T>,
+}
+
+/// A dynamic bounding volume hierarchy over items with `Rect` bounds, which handles objects of very
+/// different sizes well. Each item's bounds are outset by a margin, so that items moving a little
+/// don't need to be reinserted, and the tree is kept balanced using rotations.
+#[derive(Clone, Debug)]
+pub struct AabbTree<V, T = f32> {
+    nodes: Vec<Node<V, T>>,
+    free_nodes: Vec<usize>,
+    root: usize,
+    len: usize,
+    margin: T,
+}
+
+impl<V, T: en::Float> AabbTree<V, T> {
+    /// Creates an empty tree, which outsets each item's bounds by `margin` on every side.
+    pub fn new(margin: T) -> Self {
+        Self {
+            nodes: Vec::new(),
+            free_nodes: Vec::new(),
+            root: NONE,
+            len: 0,
+            margin,
+        }
+    }
+
+    pub fn margin(&self) -> T {
+        self.margin
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns how many levels there are below the root.
+    pub fn height(&self) -> usize {
+        self.nodes.get(self.root).map_or(0, |node| node.height)
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free_nodes.clear();
+        self.root = NONE;
+        self.len = 0;
+    }
+
+    pub fn get(&self, handle: AabbTreeHandle) -> Option<&V> {
+        match &self.nodes.get(handle.0)?.kind {
+            Kind::Leaf { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, handle: AabbTreeHandle) -> Option<&mut V> {
+        match &mut self.nodes.get_mut(handle.0)?.kind {
+            Kind::Leaf { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the bounds that the item was inserted or last updated with.
+    pub fn item_bounds(&self, handle: AabbTreeHandle) -> Option<Rect<T>> {
+        match &self.nodes.get(handle.0)?.kind {
+            Kind::Leaf { bounds, .. } => Some(*bounds),
+            _ => None,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (AabbTreeHandle, &V)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, node)| match &node.kind {
+                Kind::Leaf { value, .. } => Some((AabbTreeHandle(index), value)),
+                _ => None,
+            })
+    }
+
+    pub fn insert(&mut self, bounds: Rect<T>, value: V) -> AabbTreeHandle {
+        let leaf = self.allocate(Node {
+            bounds: bounds.outset_uniform(self.margin),
+            parent: NONE,
+            height: 0,
+            kind: Kind::Leaf { bounds, value },
+        });
+        self.insert_leaf(leaf);
+        self.len += 1;
+        AabbTreeHandle(leaf)
+    }
+
+    pub fn remove(&mut self, handle: AabbTreeHandle) -> Option<V> {
+        if !matches!(self.nodes.get(handle.0)?.kind, Kind::Leaf { .. }) {
+            return None;
+        }
+        self.remove_leaf(handle.0);
+        self.len -= 1;
+        match self.free(handle.0) {
+            Kind::Leaf { value, .. } => Some(value),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Moves the item to `bounds`, returning `false` if there's no such item. The item is only
+    /// reinserted if it moved outside of its margin.
+    pub fn update(&mut self, handle: AabbTreeHandle, bounds: Rect<T>) -> bool {
+        let node = match self.nodes.get_mut(handle.0) {
+            Some(node) => node,
+            None => return false,
+        };
+        match &mut node.kind {
+            Kind::Leaf { bounds: item, .. } => *item = bounds,
+            _ => return false,
+        }
+        if !node.bounds.contains_rect(&bounds) {
+            node.bounds = bounds.outset_uniform(self.margin);
+            self.remove_leaf(handle.0);
+            self.insert_leaf(handle.0);
+        }
+        true
+    }
+
+    /// Finds every item whose bounds overlap or touch `rect`.
+    pub fn query_rect(&self, rect: Rect<T>) -> impl Iterator<Item = (AabbTreeHandle, &V)> + '_ {
+        self.query(move |bounds| bounds.intersects(&rect))
+    }
+
+    /// Finds every item whose bounds are crossed by `ray` within `max_distance`, including items
+    /// that the ray starts inside of.
+    pub fn query_ray(
+        &self,
+        ray: &Ray<T>,
+        max_distance: T,
+    ) -> impl Iterator<Item = (AabbTreeHandle, &V)> + '_ {
+        let (point, vector) = (ray.point, ray.unit_vector());
+        self.query(move |bounds| liang_barsky(point, vector, max_distance, bounds).is_some())
+    }
+
+    /// Finds every pair of items whose bounds overlap or touch, which are the candidates for a more
+    /// precise collision test. Each pair is only reported once, with the smaller handle first.
+    pub fn candidate_pairs(&self) -> impl Iterator<Item = (AabbTreeHandle, AabbTreeHandle)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, node)| match &node.kind {
+                Kind::Leaf { bounds, .. } => Some((index, *bounds)),
+                _ => None,
+            })
+            .flat_map(move |(index, bounds)| {
+                self.query_rect(bounds)
+                    .filter(move |(other, _)| other.0 > index)
+                    .map(move |(other, _)| (AabbTreeHandle(index), other))
+            })
+    }
+
+    /// Yields the leaves whose bounds pass `test`, skipping any branches that fail it.
+    fn query<'a>(
+        &'a self,
+        test: impl Fn(&Rect<T>) -> bool + 'a,
+    ) -> impl Iterator<Item = (AabbTreeHandle, &'a V)> + 'a {
+        let mut stack = if self.root == NONE {
+            Vec::new()
+        } else {
+            vec![self.root]
+        };
+        std::iter::from_fn(move || loop {
+            let index = stack.pop()?;
+            let node = &self.nodes[index];
+            if !test(&node.bounds) {
+                continue;
+            }
+            match &node.kind {
+                Kind::Branch(children) => stack.extend(children),
+                Kind::Leaf { bounds, value } => {
+                    if test(bounds) {
+                        return Some((AabbTreeHandle(index), value));
+                    }
+                }
+                Kind::Free => unreachable!(),
+            }
+        })
+    }
+
+    fn allocate(&mut self, node: Node<V, T>) -> usize {
+        if let Some(index) = self.free_nodes.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    fn free(&mut self, index: usize) -> Kind<V, T> {
+        self.free_nodes.push(index);
+        std::mem::replace(&mut self.nodes[index].kind, Kind::Free)
+    }
+
+    fn children(&self, index: usize) -> [usize; 2] {
+        match self.nodes[index].kind {
+            Kind::Branch(children) => children,
+            _ => unreachable!(),
+        }
+    }
+
+    fn replace_child(&mut self, parent: usize, old: usize, new: usize) {
+        if parent == NONE {
+            self.root = new;
+        } else if let Kind::Branch(children) = &mut self.nodes[parent].kind {
+            for child in children {
+                if *child == old {
+                    *child = new;
+                }
+            }
+        }
+    }
+
+    /// The cost of a subtree is the total area of its branches, so adding a leaf below a node
+    /// costs however much it grows that node's bounds.
+    fn insert_leaf(&mut self, leaf: usize) {
+        if self.root == NONE {
+            self.root = leaf;
+            self.nodes[leaf].parent = NONE;
+            return;
+        }
+        let bounds = self.nodes[leaf].bounds;
+        let mut sibling = self.root;
+        while let Kind::Branch(children) = self.nodes[sibling].kind {
+            let node = &self.nodes[sibling];
+            let combined = node.bounds.union(&bounds).area();
+            // Pairing the leaf with this node would add a parent covering both of them.
+            let cost = combined + combined;
+            // Going any further down grows this node's bounds, along with the new parent's.
+            let inherited = (combined - node.bounds.area()) * T::two();
+            let descend = |child: usize| {
+                let child = &self.nodes[child];
+                let grown = child.bounds.union(&bounds).area();
+                if child.height == 0 {
+                    grown + inherited
+                } else {
+                    grown - child.bounds.area() + inherited
+                }
+            };
+            let (first, second) = (descend(children[0]), descend(children[1]));
+            if cost < first && cost < second {
+                break;
+            }
+            sibling = if first < second {
+                children[0]
+            } else {
+                children[1]
+            };
+        }
+
+        let parent = self.nodes[sibling].parent;
+        let branch = self.allocate(Node {
+            bounds: self.nodes[sibling].bounds.union(&bounds),
+            parent,
+            height: self.nodes[sibling].height + 1,
+            kind: Kind::Branch([sibling, leaf]),
+        });
+        self.replace_child(parent, sibling, branch);
+        self.nodes[sibling].parent = branch;
+        self.nodes[leaf].parent = branch;
+        self.refit(parent);
+    }
+
+    /// Detaches `leaf` from the tree without freeing it. Its parent is replaced by its sibling.
+    fn remove_leaf(&mut self, leaf: usize) {
+        let parent = self.nodes[leaf].parent;
+        if parent == NONE {
+            self.root = NONE;
+            return;
+        }
+        let children = self.children(parent);
+        let sibling = if children[0] == leaf {
+            children[1]
+        } else {
+            children[0]
+        };
+        let grandparent = self.nodes[parent].parent;
+        self.replace_child(grandparent, parent, sibling);
+        self.nodes[sibling].parent = grandparent;
+        self.free(parent);
+        self.refit(grandparent);
+    }
+
+    /// Rebalances and recomputes the bounds of `index` and all of its ancestors.
+    fn refit(&mut self, mut index: usize) {
+        while index != NONE {
+            index = self.balance(index);
+            let [a, b] = self.children(index);
+            let (a, b) = (&self.nodes[a], &self.nodes[b]);
+            let (height, bounds) = (1 + a.height.max(b.height), a.bounds.union(&b.bounds));
+            let node = &mut self.nodes[index];
+            node.height = height;
+            node.bounds = bounds;
+            index = node.parent;
+        }
+    }
+
+    /// If one of the children of `index` is more than a level taller than the other, it's rotated
+    /// up to take the place of `index`. Returns whichever node ends up in that place.
+    fn balance(&mut self, index: usize) -> usize {
+        if self.nodes[index].height < 2 {
+            return index;
+        }
+        let [a, b] = self.children(index);
+        let (a_height, b_height) = (self.nodes[a].height, self.nodes[b].height);
+        if b_height > a_height + 1 {
+            self.rotate(index, 1)
+        } else if a_height > b_height + 1 {
+            self.rotate(index, 0)
+        } else {
+            index
+        }
+    }
+
+    /// Swaps `index` with its child at `slot`. The promoted child keeps its taller child, and gives
+    /// its shorter one to `index`.
+    fn rotate(&mut self, index: usize, slot: usize) -> usize {
+        let mut children = self.children(index);
+        let (promoted, other) = (children[slot], children[1 - slot]);
+        let [c, d] = self.children(promoted);
+        let (taller, shorter) = if self.nodes[c].height > self.nodes[d].height {
+            (c, d)
+        } else {
+            (d, c)
+        };
+
+        let parent = self.nodes[index].parent;
+        self.replace_child(parent, index, promoted);
+        self.nodes[promoted].parent = parent;
+        self.nodes[promoted].kind = Kind::Branch([index, taller]);
+        self.nodes[index].parent = promoted;
+        children[slot] = shorter;
+        self.nodes[index].kind = Kind::Branch(children);
+        self.nodes[shorter].parent = index;
+
+        let (other, shorter) = (&self.nodes[other], &self.nodes[shorter]);
+        let (height, bounds) = (
+            1 + other.height.max(shorter.height),
+            other.bounds.union(&shorter.bounds),
+        );
+        self.nodes[index].height = height;
+        self.nodes[index].bounds = bounds;
+        let (node, taller) = (&self.nodes[index], &self.nodes[taller]);
+        let (height, bounds) = (
+            1 + node.height.max(taller.height),
+            node.bounds.union(&taller.bounds),
+        );
+        self.nodes[promoted].height = height;
+        self.nodes[promoted].bounds = bounds;
+        promoted
+    }
+}
+
+impl<V: Raycast<T>, T: en::Float> AabbTree<V, T> {
+    /// Returns the item that `ray` hits first along with where it hit, as long as it's no more than
+    /// `max_distance` away. Branches farther away than the nearest hit so far are skipped.
+    pub fn cast_ray(&self, ray: &Ray<T>, max_distance: T) -> Option<(AabbTreeHandle, RayHit<T>)> {
+        let (point, vector) = (ray.point, ray.unit_vector());
+        let mut nearest = None;
+        let mut max_distance = max_distance;
+        let mut stack = if self.root == NONE {
+            Vec::new()
+        } else {
+            vec![self.root]
+        };
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if liang_barsky(point, vector, max_distance, &node.bounds).is_none() {
+                continue;
+            }
+            match &node.kind {
+                Kind::Branch(children) => stack.extend(children),
+                Kind::Leaf { value, .. } => {
+                    if let Some(hit) = value.raycast(ray, max_distance) {
+                        max_distance = hit.distance;
+                        nearest = Some((AabbTreeHandle(index), hit));
+                    }
+                }
+                Kind::Free => unreachable!(),
+            }
+        }
+        nearest
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        assert_approx_eq,
+        test::{sorted, square},
+        Angle, Point,
+    };
+
+    #[test]
+    fn queries() {
+        let mut tree = AabbTree::new(1.0);
+        let a = tree.insert(square(0.0, 0.0, 2.0), 0);
+        let b = tree.insert(square(10.0, 0.0, 50.0), 1);
+        tree.insert(square(4.0, 4.0, 1.0), 2);
+        // The margin doesn't count when matching items.
+        assert_eq!(sorted(tree.query_rect(square(2.5, 2.5, 1.0))), vec![]);
+        assert_eq!(
+            sorted(tree.query_rect(square(2.0, 2.0, 9.0))),
+            vec![0, 1, 2]
+        );
+        let ray = Ray::new(Point::new(1.0, 1.0), Angle::ZERO());
+        assert_eq!(sorted(tree.query_ray(&ray, 5.0)), vec![0]);
+        assert_eq!(sorted(tree.query_ray(&ray, 10.0)), vec![0, 1]);
+
+        // Moving within the margin keeps the item where it was.
+        assert!(tree.update(a, square(0.5, 0.5, 2.0)));
+        assert_eq!(tree.item_bounds(a), Some(square(0.5, 0.5, 2.0)));
+        assert!(tree.update(b, square(-100.0, -100.0, 1.0)));
+        assert_eq!(
+            sorted(tree.query_rect(square(-100.0, -100.0, 1.0))),
+            vec![1]
+        );
+        assert_eq!(tree.remove(b), Some(1));
+        assert_eq!(tree.remove(b), None);
+        assert!(!tree.update(b, square(0.0, 0.0, 1.0)));
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn cast_ray() {
+        let mut tree = AabbTree::new(0.5);
+        let far = tree.insert(square(20.0, -5.0, 10.0), square(20.0, -5.0, 10.0));
+        let near = tree.insert(square(10.0, -1.0, 2.0), square(10.0, -1.0, 2.0));
+        tree.insert(square(10.0, 5.0, 2.0), square(10.0, 5.0, 2.0));
+        let ray = Ray::new(Point::zero(), Angle::ZERO());
+        let (handle, hit) = tree.cast_ray(&ray, 100.0).unwrap();
+        assert_eq!(handle, near);
+        assert_approx_eq!(hit.distance, 10.0);
+        tree.remove(near);
+        assert_eq!(tree.cast_ray(&ray, 100.0).unwrap().0, far);
+        assert_eq!(tree.cast_ray(&ray, 15.0), None);
+    }
+
+    #[test]
+    fn candidate_pairs() {
+        let mut tree = AabbTree::new(2.0);
+        let a = tree.insert(square(8.0, 8.0, 4.0), ());
+        let b = tree.insert(square(11.0, 11.0, 4.0), ());
+        let c = tree.insert(square(1.0, 1.0, 8.0), ());
+        // These are only close enough to overlap once they're fattened, so they aren't a pair.
+        tree.insert(square(30.0, 30.0, 1.0), ());
+        tree.insert(square(32.0, 30.0, 1.0), ());
+        let mut pairs: Vec<_> = tree.candidate_pairs().collect();
+        pairs.sort_unstable();
+        assert_eq!(pairs, vec![(a, b), (a, c)]);
+    }
+
+    #[test]
+    fn balanced() {
+        // Inserting in order would make a very lopsided tree without any rotations.
+        let mut tree = AabbTree::new(0.0);
+        let handles: Vec<_> = (0..1024)
+            .map(|index| tree.insert(square(index as f32 * 2.0, 0.0, 1.0), index))
+            .collect();
+        assert!(tree.height() <= 20, "height was {}", tree.height());
+        for handle in handles.into_iter().step_by(2) {
+            tree.remove(handle);
+        }
+        assert!(tree.height() <= 18, "height was {}", tree.height());
+        assert_eq!(sorted(tree.query_rect(square(10.0, 0.0, 5.0))), vec![5, 7]);
+    }
+}
@@ -0,0 +1,762 @@
+use crate::{Point, Polyline, Rect, Transform, Vector};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The nodes and weights of 5-point Gauss-Legendre quadrature over `-1..=1`.
+const GAUSS_LEGENDRE: [(f64, f64); 5] = [
+    (0.0, 0.568_888_888_888_888_9),
+    (-0.538_469_310_105_683, 0.478_628_670_499_366_5),
+    (0.538_469_310_105_683, 0.478_628_670_499_366_5),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+];
+
+fn mix<T: en::Float>(a: Point<T>, b: Point<T>, t: T) -> Point<T> {
+    a + (b - a) * t
+}
+
+/// Returns `true` if `t` is inside the curve, not counting its endpoints.
+fn is_interior<T: en::Float>(t: &T) -> bool {
+    *t > T::zero() && *t < T::one()
+}
+
+/// Finds the real roots of `at² + bt + c`, which are `NaN` or infinite if they don't exist.
+fn quadratic_roots<T: en::Float>(a: T, b: T, c: T) -> [T; 2] {
+    let discriminant = b * b - T::two().doubled() * a * c;
+    if discriminant < T::zero() {
+        return [T::nan(); 2];
+    }
+    // This avoids cancellation between `b` and the root, and handles `a` being zero.
+    let root = discriminant.sqrt();
+    let q = -(b + if b < T::zero() { -root } else { root }).halved();
+    [q / a, c / q]
+}
+
+/// Checks if a curve stays within `tolerance` of the line from `from` to `to`, given that it's
+/// inside the hull of `controls` and strays from the line by at most `scale` times as much as the
+/// furthest of them.
+fn hull_is_flat<T: en::Float>(
+    from: Point<T>,
+    to: Point<T>,
+    controls: &[Point<T>],
+    scale: T,
+    tolerance: T,
+) -> bool {
+    let chord = to - from;
+    let length_squared = chord.magnitude_squared();
+    if length_squared == T::zero() {
+        return controls
+            .iter()
+            .all(|control| (*control - from).magnitude() <= tolerance);
+    }
+    // If the controls don't reach past either end of the line, then neither does the curve, so its
+    // distance from the line is all that matters.
+    controls.iter().all(|control| {
+        let offset = *control - from;
+        let along = offset.dot_product(chord);
+        along >= T::zero()
+            && along <= length_squared
+            && chord.cross_product(offset).abs() * scale <= tolerance * length_squared.sqrt()
+    })
+}
+
+/// Integrates `f` over `start..=end`, splitting the range until the estimate is within `tolerance`.
+fn integrate<T: en::Float>(f: &impl Fn(T) -> T, start: T, end: T, tolerance: T) -> T {
+    fn estimate<T: en::Float>(f: &impl Fn(T) -> T, start: T, end: T) -> T {
+        let (middle, half) = ((start + end).halved(), (end - start).halved());
+        GAUSS_LEGENDRE
+            .iter()
+            .fold(T::zero(), |sum, (node, weight)| {
+                sum + f(middle + half * en::cast(*node)) * en::cast(*weight)
+            })
+            * half
+    }
+
+    fn refine<T: en::Float>(
+        f: &impl Fn(T) -> T,
+        start: T,
+        end: T,
+        whole: T,
+        tolerance: T,
+        depth: usize,
+    ) -> T {
+        let middle = (start + end).halved();
+        let (left, right) = (estimate(f, start, middle), estimate(f, middle, end));
+        if depth == 0 || (left + right - whole).abs() <= tolerance {
+            left + right
+        } else {
+            let tolerance = tolerance.halved();
+            refine(f, start, middle, left, tolerance, depth - 1)
+                + refine(f, middle, end, right, tolerance, depth - 1)
+        }
+    }
+
+    refine(f, start, end, estimate(f, start, end), tolerance, 16)
+}
+
+/// The shared machinery for curves that are only defined by how to evaluate them.
+pub(crate) trait Curve<T: en::Float> {
+    fn position(&self, t: T) -> Point<T>;
+
+    fn velocity(&self, t: T) -> Vector<T>;
+
+    fn acceleration(&self, t: T) -> Vector<T>;
+
+    /// The length of the control polygon, which is never shorter than the curve.
+    fn hull_length(&self) -> T;
+
+    /// Checks if the line between the points at `start` and `end` stays within `tolerance` of the
+    /// part of the curve between them.
+    fn is_flat(&self, start: T, end: T, tolerance: T) -> bool;
+
+    fn tolerance(&self) -> T {
+        self.hull_length() * T::epsilon() * en::cast(256)
+    }
+
+    fn length_between(&self, start: T, end: T) -> T {
+        integrate(
+            &|t| self.velocity(t).magnitude(),
+            start,
+            end,
+            self.tolerance(),
+        )
+    }
+
+    /// Uses Newton's method to find where the length along the curve reaches `distance`, falling
+    /// back to bisection wherever that wouldn't converge.
+    fn t_at_length(&self, distance: T) -> T {
+        let length = self.length_between(T::zero(), T::one());
+        if distance <= T::zero() {
+            return T::zero();
+        } else if distance >= length {
+            return T::one();
+        }
+        let tolerance = self.tolerance();
+        let (mut low, mut high) = (T::zero(), T::one());
+        let mut t = distance / length;
+        for _ in 0..32 {
+            let error = self.length_between(T::zero(), t) - distance;
+            if error.abs() <= tolerance {
+                break;
+            } else if error > T::zero() {
+                high = t;
+            } else {
+                low = t;
+            }
+            let next = t - error / self.velocity(t).magnitude();
+            t = if next > low && next < high {
+                next
+            } else {
+                (low + high).halved()
+            };
+        }
+        t
+    }
+
+    /// Walks along the curve, making each line reach as far as it can while staying flat enough.
+    /// Taking the longest step every time leaves as few points as possible, so they're only close
+    /// together where the curve bends sharply.
+    fn flatten(&self, tolerance: T) -> Polyline<T> {
+        // How many times to bisect each step. This also limits how short the steps can get when
+        // nothing is flat enough, such as when `tolerance` is zero.
+        let precision = 16;
+        let min_step = T::one() / en::cast(1 << precision);
+        let mut points = vec![self.position(T::zero())];
+        let mut start = T::zero();
+        while start < T::one() {
+            let (mut low, mut high) = (start, T::one());
+            if self.is_flat(start, high, tolerance) {
+                low = high;
+            } else {
+                for _ in 0..precision {
+                    let middle = (low + high).halved();
+                    if self.is_flat(start, middle, tolerance) {
+                        low = middle;
+                    } else {
+                        high = middle;
+                    }
+                }
+            }
+            start = en::Num::max(low, en::Num::min(start + min_step, T::one()));
+            points.push(self.position(start));
+        }
+        Polyline::new(points)
+    }
+
+    /// Checks evenly spaced samples for the closest one, and then polishes each local minimum with
+    /// Newton's method.
+    fn closest_t(&self, point: Point<T>, samples: usize) -> T {
+        let distance = |t: T| (self.position(t) - point).magnitude_squared();
+        let at = |index: usize| en::cast::<T, _>(index) / en::cast(samples);
+        let distances: Vec<T> = (0..=samples).map(|index| distance(at(index))).collect();
+        let mut closest = (T::zero(), distances[0]);
+        for index in 0..=samples {
+            let is_minimum = (index == 0 || distances[index - 1] >= distances[index])
+                && (index == samples || distances[index + 1] >= distances[index]);
+            if !is_minimum {
+                continue;
+            }
+            let (low, high) = (at(index.saturating_sub(1)), at((index + 1).min(samples)));
+            let mut t = at(index);
+            for _ in 0..8 {
+                let offset = self.position(t) - point;
+                let velocity = self.velocity(t);
+                let slope = offset.dot_product(velocity);
+                let curvature =
+                    velocity.magnitude_squared() + offset.dot_product(self.acceleration(t));
+                if curvature <= T::zero() {
+                    break;
+                }
+                let next = t - slope / curvature;
+                t = en::Num::min(en::Num::max(next, low), high);
+            }
+            let candidate = distance(t);
+            if candidate < closest.1 {
+                closest = (t, candidate);
+            }
+        }
+        closest.0
+    }
+}
+
+/// A quadratic Bézier curve, which starts at `from`, heads towards `ctrl`, and ends at `to`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct QuadBezier<T = f32> {
+    pub from: Point<T>,
+    pub ctrl: Point<T>,
+    pub to: Point<T>,
+}
+
+impl<T: en::Num> QuadBezier<T> {
+    pub fn new(from: Point<T>, ctrl: Point<T>, to: Point<T>) -> Self {
+        Self { from, ctrl, to }
+    }
+
+    /// Returns the same curve with its direction reversed.
+    pub fn reverse(self) -> Self {
+        Self::new(self.to, self.ctrl, self.from)
+    }
+
+    /// Transforming the control points transforms the whole curve, since Bézier curves are
+    /// preserved by affine transformations.
+    pub fn transform(self, transform: Transform<T>) -> Self {
+        self.map(move |point| point.transform(transform))
+    }
+
+    pub fn map<U: en::Num>(self, mut f: impl FnMut(Point<T>) -> Point<U>) -> QuadBezier<U> {
+        QuadBezier::new(f(self.from), f(self.ctrl), f(self.to))
+    }
+
+    pub fn cast<U: en::Num>(self) -> QuadBezier<U> {
+        self.map(Point::cast)
+    }
+
+    impl_casts!(QuadBezier);
+}
+
+impl<T: en::Float> Curve<T> for QuadBezier<T> {
+    fn position(&self, t: T) -> Point<T> {
+        self.point_at(t)
+    }
+
+    fn velocity(&self, t: T) -> Vector<T> {
+        self.derivative(t)
+    }
+
+    fn acceleration(&self, _t: T) -> Vector<T> {
+        ((self.to - self.ctrl) - (self.ctrl - self.from)) * T::two()
+    }
+
+    /// The curve strays from the line between its ends by at most half as much as its control
+    /// point does.
+    fn is_flat(&self, start: T, end: T, tolerance: T) -> bool {
+        let piece = self.piece(start, end);
+        hull_is_flat(
+            piece.from,
+            piece.to,
+            &[piece.ctrl],
+            T::one().halved(),
+            tolerance,
+        )
+    }
+
+    fn hull_length(&self) -> T {
+        (self.ctrl - self.from).magnitude() + (self.to - self.ctrl).magnitude()
+    }
+}
+
+impl<T: en::Float> QuadBezier<T> {
+    /// Returns the point `t` of the way along the curve, where `t` is in `0..=1`.
+    pub fn point_at(&self, t: T) -> Point<T> {
+        mix(mix(self.from, self.ctrl, t), mix(self.ctrl, self.to, t), t)
+    }
+
+    /// Returns the velocity of a point moving along the curve as `t` goes from 0 to 1.
+    pub fn derivative(&self, t: T) -> Vector<T> {
+        ((self.ctrl - self.from) * (T::one() - t) + (self.to - self.ctrl) * t) * T::two()
+    }
+
+    /// Returns the unit vector pointing along the curve. Where the derivative is zero, which only
+    /// happens at an endpoint that's the same as the control point, this points from `from` to `to`.
+    pub fn tangent(&self, t: T) -> Vector<T> {
+        let derivative = self.derivative(t);
+        if derivative == Vector::zero() {
+            (self.to - self.from).normalize()
+        } else {
+            derivative.normalize()
+        }
+    }
+
+    /// Returns the unit vector perpendicular to the curve, pointing to the right.
+    pub fn normal(&self, t: T) -> Vector<T> {
+        self.tangent(t).perpendicular()
+    }
+
+    /// Splits the curve in two at `t`, using de Casteljau's algorithm.
+    pub fn split(&self, t: T) -> (Self, Self) {
+        let (a, b) = (mix(self.from, self.ctrl, t), mix(self.ctrl, self.to, t));
+        let middle = mix(a, b, t);
+        (
+            Self::new(self.from, a, middle),
+            Self::new(middle, b, self.to),
+        )
+    }
+
+    /// Returns the part of the curve between `start` and `end`, where `start` is less than 1.
+    fn piece(&self, start: T, end: T) -> Self {
+        let (_, rest) = self.split(start);
+        rest.split((end - start) / (T::one() - start)).0
+    }
+
+    /// Returns the smallest rect containing the curve, which is usually smaller than the one
+    /// containing its control points.
+    pub fn bounding_rect(&self) -> Rect<T> {
+        // The curve only turns around once along each axis, where the derivative is zero.
+        let extremum = |from: T, ctrl: T, to: T| (from - ctrl) / (from - ctrl - ctrl + to);
+        let extrema = [
+            extremum(self.from.x, self.ctrl.x, self.to.x),
+            extremum(self.from.y, self.ctrl.y, self.to.y),
+        ];
+        Rect::from_iter(
+            [self.from, self.to].into_iter().chain(
+                extrema
+                    .into_iter()
+                    .filter(is_interior)
+                    .map(|t| self.point_at(t)),
+            ),
+        )
+    }
+
+    pub fn length(&self) -> T {
+        self.length_between(T::zero(), T::one())
+    }
+
+    /// Returns the `t` where the length of the curve up to that point is `distance`. Stepping
+    /// `distance` evenly moves along the curve at a constant speed, which stepping `t` doesn't.
+    pub fn t_at_length(&self, distance: T) -> T {
+        Curve::t_at_length(self, distance)
+    }
+
+    /// Returns the point that's `distance` along the curve, clamped to its endpoints.
+    pub fn point_at_length(&self, distance: T) -> Point<T> {
+        self.point_at(self.t_at_length(distance))
+    }
+
+    /// Returns the `t` of the point on the curve that's closest to `point`.
+    pub fn closest_t(&self, point: Point<T>) -> T {
+        Curve::closest_t(self, point, 8)
+    }
+
+    /// Approximates the curve with a polyline that strays from it by no more than `tolerance`,
+    /// which should be positive. The polyline includes both endpoints, and uses fewer points where
+    /// the curve is flatter.
+    pub fn flatten(&self, tolerance: T) -> Polyline<T> {
+        Curve::flatten(self, tolerance)
+    }
+}
+
+/// A cubic Bézier curve, which starts at `from` heading towards `ctrl1`, and ends at `to` coming
+/// from the direction of `ctrl2`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct CubicBezier<T = f32> {
+    pub from: Point<T>,
+    pub ctrl1: Point<T>,
+    pub ctrl2: Point<T>,
+    pub to: Point<T>,
+}
+
+impl<T: en::Num> CubicBezier<T> {
+    pub fn new(from: Point<T>, ctrl1: Point<T>, ctrl2: Point<T>, to: Point<T>) -> Self {
+        Self {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        }
+    }
+
+    /// Returns the same curve with its direction reversed.
+    pub fn reverse(self) -> Self {
+        Self::new(self.to, self.ctrl2, self.ctrl1, self.from)
+    }
+
+    /// Transforming the control points transforms the whole curve, since Bézier curves are
+    /// preserved by affine transformations.
+    pub fn transform(self, transform: Transform<T>) -> Self {
+        self.map(move |point| point.transform(transform))
+    }
+
+    pub fn map<U: en::Num>(self, mut f: impl FnMut(Point<T>) -> Point<U>) -> CubicBezier<U> {
+        CubicBezier::new(f(self.from), f(self.ctrl1), f(self.ctrl2), f(self.to))
+    }
+
+    pub fn cast<U: en::Num>(self) -> CubicBezier<U> {
+        self.map(Point::cast)
+    }
+
+    impl_casts!(CubicBezier);
+}
+
+impl<T: en::Float> Curve<T> for CubicBezier<T> {
+    fn position(&self, t: T) -> Point<T> {
+        self.point_at(t)
+    }
+
+    fn velocity(&self, t: T) -> Vector<T> {
+        self.derivative(t)
+    }
+
+    fn acceleration(&self, t: T) -> Vector<T> {
+        let (a, b, c) = (
+            self.ctrl1 - self.from,
+            self.ctrl2 - self.ctrl1,
+            self.to - self.ctrl2,
+        );
+        ((b - a) * (T::one() - t) + (c - b) * t) * (T::two() * T::three())
+    }
+
+    /// The curve strays from the line between its ends by at most 3/4 as much as the furthest of
+    /// its control points, or 4/9 if they're on opposite sides of it.
+    fn is_flat(&self, start: T, end: T, tolerance: T) -> bool {
+        let piece = self.piece(start, end);
+        let chord = piece.to - piece.from;
+        let same_side = chord.cross_product(piece.ctrl1 - piece.from)
+            * chord.cross_product(piece.ctrl2 - piece.from)
+            > T::zero();
+        let scale = if same_side {
+            en::cast::<T, _>(3) / en::cast(4)
+        } else {
+            en::cast::<T, _>(4) / en::cast(9)
+        };
+        hull_is_flat(
+            piece.from,
+            piece.to,
+            &[piece.ctrl1, piece.ctrl2],
+            scale,
+            tolerance,
+        )
+    }
+
+    fn hull_length(&self) -> T {
+        (self.ctrl1 - self.from).magnitude()
+            + (self.ctrl2 - self.ctrl1).magnitude()
+            + (self.to - self.ctrl2).magnitude()
+    }
+}
+
+impl<T: en::Float> CubicBezier<T> {
+    /// Returns the point `t` of the way along the curve, where `t` is in `0..=1`.
+    pub fn point_at(&self, t: T) -> Point<T> {
+        let (a, b, c) = (
+            mix(self.from, self.ctrl1, t),
+            mix(self.ctrl1, self.ctrl2, t),
+            mix(self.ctrl2, self.to, t),
+        );
+        mix(mix(a, b, t), mix(b, c, t), t)
+    }
+
+    /// Returns the velocity of a point moving along the curve as `t` goes from 0 to 1.
+    pub fn derivative(&self, t: T) -> Vector<T> {
+        let s = T::one() - t;
+        ((self.ctrl1 - self.from) * (s * s)
+            + (self.ctrl2 - self.ctrl1) * (T::two() * s * t)
+            + (self.to - self.ctrl2) * (t * t))
+            * T::three()
+    }
+
+    /// Returns the unit vector pointing along the curve. Where the derivative is zero, e.g. at an
+    /// endpoint that's the same as its control point, this points along the control polygon
+    /// instead.
+    pub fn tangent(&self, t: T) -> Vector<T> {
+        let derivative = self.derivative(t);
+        if derivative != Vector::zero() {
+            return derivative.normalize();
+        }
+        let candidates = if t < T::one().halved() {
+            [self.ctrl2 - self.from, self.to - self.from]
+        } else {
+            [self.to - self.ctrl1, self.to - self.from]
+        };
+        candidates
+            .into_iter()
+            .find(|vector| *vector != Vector::zero())
+            .unwrap_or(candidates[1])
+            .normalize()
+    }
+
+    /// Returns the unit vector perpendicular to the curve, pointing to the right.
+    pub fn normal(&self, t: T) -> Vector<T> {
+        self.tangent(t).perpendicular()
+    }
+
+    /// Splits the curve in two at `t`, using de Casteljau's algorithm.
+    pub fn split(&self, t: T) -> (Self, Self) {
+        let (a, b, c) = (
+            mix(self.from, self.ctrl1, t),
+            mix(self.ctrl1, self.ctrl2, t),
+            mix(self.ctrl2, self.to, t),
+        );
+        let (d, e) = (mix(a, b, t), mix(b, c, t));
+        let middle = mix(d, e, t);
+        (
+            Self::new(self.from, a, d, middle),
+            Self::new(middle, e, c, self.to),
+        )
+    }
+
+    /// Returns the part of the curve between `start` and `end`, where `start` is less than 1.
+    fn piece(&self, start: T, end: T) -> Self {
+        let (_, rest) = self.split(start);
+        rest.split((end - start) / (T::one() - start)).0
+    }
+
+    /// Returns the smallest rect containing the curve, which is usually smaller than the one
+    /// containing its control points.
+    pub fn bounding_rect(&self) -> Rect<T> {
+        // The derivative along each axis is quadratic, so the curve turns around at most twice.
+        let extrema = |from: T, ctrl1: T, ctrl2: T, to: T| {
+            let (a, b, c) = (ctrl1 - from, ctrl2 - ctrl1, to - ctrl2);
+            quadratic_roots(a - b - b + c, (b - a).doubled(), a)
+        };
+        let [x1, x2] = extrema(self.from.x, self.ctrl1.x, self.ctrl2.x, self.to.x);
+        let [y1, y2] = extrema(self.from.y, self.ctrl1.y, self.ctrl2.y, self.to.y);
+        Rect::from_iter(
+            [self.from, self.to].into_iter().chain(
+                [x1, x2, y1, y2]
+                    .into_iter()
+                    .filter(is_interior)
+                    .map(|t| self.point_at(t)),
+            ),
+        )
+    }
+
+    pub fn length(&self) -> T {
+        self.length_between(T::zero(), T::one())
+    }
+
+    /// Returns the `t` where the length of the curve up to that point is `distance`. Stepping
+    /// `distance` evenly moves along the curve at a constant speed, which stepping `t` doesn't.
+    pub fn t_at_length(&self, distance: T) -> T {
+        Curve::t_at_length(self, distance)
+    }
+
+    /// Returns the point that's `distance` along the curve, clamped to its endpoints.
+    pub fn point_at_length(&self, distance: T) -> Point<T> {
+        self.point_at(self.t_at_length(distance))
+    }
+
+    /// Returns the `t` of the point on the curve that's closest to `point`.
+    pub fn closest_t(&self, point: Point<T>) -> T {
+        Curve::closest_t(self, point, 16)
+    }
+
+    /// Approximates the curve with a polyline that strays from it by no more than `tolerance`,
+    /// which should be positive. The polyline includes both endpoints, and uses fewer points where
+    /// the curve is flatter.
+    pub fn flatten(&self, tolerance: T) -> Polyline<T> {
+        Curve::flatten(self, tolerance)
+    }
+}
+
+impl<T: en::Float> From<QuadBezier<T>> for CubicBezier<T> {
+    /// Every quadratic curve is also a cubic curve, with control points two thirds of the way to
+    /// the quadratic control point.
+    fn from(quad: QuadBezier<T>) -> Self {
+        let third = T::one() / T::three();
+        Self::new(
+            quad.from,
+            mix(quad.ctrl, quad.from, third),
+            mix(quad.ctrl, quad.to, third),
+            quad.to,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, assert_points_eq, Angle, Distance, Size};
+
+    fn quad() -> QuadBezier {
+        QuadBezier::new(
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 4.0),
+            Point::new(4.0, 0.0),
+        )
+    }
+
+    fn cubic() -> CubicBezier {
+        CubicBezier::new(
+            Point::new(0.0, 0.0),
+            Point::new(-2.0, 4.0),
+            Point::new(8.0, -2.0),
+            Point::new(6.0, 2.0),
+        )
+    }
+
+    #[test]
+    fn evaluate() {
+        let quad = quad();
+        assert_points_eq!(quad.point_at(0.5), Point::new(2.0, 2.0));
+        assert_points_eq!(quad.tangent(0.5).to_point(), Point::new(1.0, 0.0));
+        // Looking right with y pointing down, the right side is down.
+        assert_points_eq!(quad.normal(0.5).to_point(), Point::new(0.0, 1.0));
+        let cubic = CubicBezier::from(quad);
+        for t in [0.0, 0.3, 0.5, 0.8, 1.0] {
+            assert_points_eq!(cubic.point_at(t), quad.point_at(t));
+            assert_points_eq!(
+                cubic.derivative(t).to_point(),
+                quad.derivative(t).to_point(),
+            );
+        }
+        // The derivative is zero at the start, but the curve still heads towards `ctrl2`.
+        let cusp = CubicBezier::new(
+            Point::zero(),
+            Point::zero(),
+            Point::new(0.0, 3.0),
+            Point::new(3.0, 3.0),
+        );
+        assert_points_eq!(cusp.tangent(0.0).to_point(), Point::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn split() {
+        let cubic = cubic();
+        let (a, b) = cubic.split(0.25);
+        assert_eq!(a.from, cubic.from);
+        assert_eq!(b.to, cubic.to);
+        assert_points_eq!(a.to, cubic.point_at(0.25));
+        assert_points_eq!(a.point_at(0.5), cubic.point_at(0.125));
+        assert_points_eq!(b.point_at(0.5), cubic.point_at(0.625));
+        let (a, b) = quad().split(0.5);
+        assert_points_eq!(a.point_at(0.5), quad().point_at(0.25));
+        assert_points_eq!(b.point_at(0.5), quad().point_at(0.75));
+    }
+
+    #[test]
+    fn bounding_rect() {
+        assert_eq!(
+            quad().bounding_rect(),
+            Rect::from_top_left(Point::zero(), Size::new(4.0, 2.0))
+        );
+        // The curve bulges past both of its endpoints horizontally, but not as far as the controls.
+        let rect = cubic().bounding_rect();
+        let samples =
+            Rect::from_iter((0..=1000).map(|index| cubic().point_at(index as f32 / 1000.0)));
+        assert!(rect.left() < 0.0 && rect.right() > 6.0);
+        assert!(rect.left() > -2.0 && rect.right() < 8.0);
+        assert!(rect.contains_rect(&samples));
+        assert!(samples.outset_uniform(0.001).contains_rect(&rect));
+    }
+
+    #[test]
+    fn transform() {
+        let transform =
+            Transform::from_rotation(Angle::FRAC_PI_2(), Point::new(1.0, 1.0)).post_scale(2.0, 3.0);
+        let cubic = cubic();
+        let transformed = cubic.transform(transform);
+        assert_points_eq!(
+            transformed.point_at(0.4),
+            cubic.point_at(0.4).transform(transform),
+        );
+    }
+
+    #[test]
+    fn length() {
+        let line = QuadBezier::new(Point::zero(), Point::new(1.0, 0.0), Point::new(5.0, 0.0));
+        assert_approx_eq!(line.length(), 5.0);
+        // Even though `t` doesn't advance evenly along this curve, the length does.
+        assert_points_eq!(line.point_at_length(2.0), Point::new(2.0, 0.0));
+        assert_points_eq!(line.point_at_length(7.0), line.to);
+
+        // A cubic approximation of a quarter of a unit circle.
+        let k = 0.552_284_8;
+        let arc = CubicBezier::new(
+            Point::new(1.0, 0.0),
+            Point::new(1.0, k),
+            Point::new(k, 1.0),
+            Point::new(0.0, 1.0),
+        );
+        assert!((arc.length() - std::f32::consts::FRAC_PI_2).abs() < 0.001);
+        let middle = arc.point_at_length(arc.length() / 2.0);
+        assert_approx_eq!(middle.x, middle.y);
+        let t = arc.t_at_length(1.0);
+        assert!((arc.length_between(0.0, t) - 1.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn closest_t() {
+        let quad = quad();
+        assert_approx_eq!(quad.closest_t(Point::new(2.0, 5.0)), 0.5);
+        assert_approx_eq!(quad.closest_t(Point::new(-3.0, -1.0)), 0.0);
+        let cubic = cubic();
+        let t = cubic.closest_t(Point::new(3.0, 3.0));
+        let closest = cubic.point_at(t);
+        // The offset to the closest point is perpendicular to the curve.
+        assert_approx_eq!(
+            (Point::new(3.0, 3.0) - closest).dot_product(cubic.tangent(t)),
+            0.0
+        );
+    }
+
+    #[test]
+    fn flatten() {
+        for tolerance in [0.1, 0.01] {
+            // The quadratic curve is checked against its cubic equivalent.
+            let flattened = [
+                (CubicBezier::from(quad()), quad().flatten(tolerance)),
+                (cubic(), cubic().flatten(tolerance)),
+            ];
+            for (curve, polyline) in flattened {
+                assert_eq!(polyline.points().first(), Some(&curve.from));
+                assert_eq!(polyline.points().last(), Some(&curve.to));
+                for index in 0..=1000 {
+                    let point = curve.point_at(index as f32 / 1000.0);
+                    assert!(polyline.distance_to(point) <= tolerance);
+                }
+            }
+        }
+        // Tighter tolerances need more points.
+        assert!(cubic().flatten(0.01).len() > cubic().flatten(0.1).len());
+        // The points bunch up where the curve bends.
+        let bend = CubicBezier::new(
+            Point::new(0.0, 0.0),
+            Point::new(90.0, 0.0),
+            Point::new(100.0, 0.0),
+            Point::new(100.0, 10.0),
+        );
+        let points = bend.flatten(0.01).into_points();
+        let (first, last) = (
+            points[1] - points[0],
+            points[points.len() - 1] - points[points.len() - 2],
+        );
+        assert!(first.magnitude() > last.magnitude() * 10.0);
+
+        let line = QuadBezier::new(Point::zero(), Point::new(1.0, 1.0), Point::new(2.0, 2.0));
+        assert_eq!(line.flatten(0.1).len(), 2);
+    }
+}
@@ -0,0 +1,633 @@
+use crate::{orient2d, LineSegment, MultiPolygon, Point, Polygon, PolygonWithHoles, Vector};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BooleanOp {
+    /// Everything covered by either shape.
+    Union,
+    /// Everything covered by both shapes.
+    Intersection,
+    /// Everything covered by the first shape but not the second.
+    Difference,
+    /// Everything covered by exactly one of the shapes.
+    Xor,
+}
+
+impl BooleanOp {
+    pub fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Self::Union => a || b,
+            Self::Intersection => a && b,
+            Self::Difference => a && !b,
+            Self::Xor => a != b,
+        }
+    }
+}
+
+/// A borrowed view of the polygons making up one side of a boolean operation.
+struct Operand<'a, T> {
+    parts: Vec<(&'a Polygon<T>, &'a [Polygon<T>])>,
+}
+
+impl<'a, T: en::Float> Operand<'a, T> {
+    fn rings(&self) -> impl Iterator<Item = &'a [Point<T>]> + '_ {
+        self.parts.iter().flat_map(|(outer, holes)| {
+            std::iter::once(outer.points()).chain(holes.iter().map(Polygon::points))
+        })
+    }
+
+    fn ring_count(&self) -> usize {
+        self.parts.iter().map(|(_, holes)| holes.len() + 1).sum()
+    }
+
+    /// Given the winding number of each ring around some point, checks if the point is covered.
+    fn covers(&self, windings: &[i32]) -> bool {
+        let mut windings = windings.iter();
+        let mut covered = false;
+        for (_, holes) in &self.parts {
+            let in_outer = windings.next().is_some_and(|w| *w != 0);
+            let in_hole = windings.by_ref().take(holes.len()).any(|w| *w != 0);
+            covered |= in_outer && !in_hole;
+        }
+        covered
+    }
+}
+
+impl<'a, T> From<&'a Polygon<T>> for Operand<'a, T> {
+    fn from(polygon: &'a Polygon<T>) -> Self {
+        Self {
+            parts: vec![(polygon, &[])],
+        }
+    }
+}
+
+impl<'a, T> From<&'a PolygonWithHoles<T>> for Operand<'a, T> {
+    fn from(polygon: &'a PolygonWithHoles<T>) -> Self {
+        Self {
+            parts: vec![(&polygon.outer, &polygon.holes)],
+        }
+    }
+}
+
+impl<'a, T> From<&'a MultiPolygon<T>> for Operand<'a, T> {
+    fn from(polygons: &'a MultiPolygon<T>) -> Self {
+        Self {
+            parts: polygons
+                .polygons
+                .iter()
+                .map(|polygon| (&polygon.outer, polygon.holes.as_slice()))
+                .collect(),
+        }
+    }
+}
+
+fn boolean<T: en::Float>(a: Operand<T>, b: Operand<T>, op: BooleanOp) -> MultiPolygon<T> {
+    let rings: Vec<_> = a.rings().chain(b.rings()).collect();
+    let split = a.ring_count();
+    overlay(&rings, |windings| {
+        let (a_windings, b_windings) = windings.split_at(split);
+        op.apply(a.covers(a_windings), b.covers(b_windings))
+    })
+}
+
+macro_rules! impl_boolean_ops {
+    ($ty:ident) => {
+        impl<T: en::Float> $ty<T> {
+            /// Combines this shape with `other`. The result's outer rings are wound clockwise and
+            /// its holes are wound counter-clockwise.
+            pub fn boolean(&self, other: &Self, op: BooleanOp) -> MultiPolygon<T> {
+                boolean(Operand::from(self), Operand::from(other), op)
+            }
+
+            pub fn union(&self, other: &Self) -> MultiPolygon<T> {
+                self.boolean(other, BooleanOp::Union)
+            }
+
+            pub fn intersection(&self, other: &Self) -> MultiPolygon<T> {
+                self.boolean(other, BooleanOp::Intersection)
+            }
+
+            pub fn difference(&self, other: &Self) -> MultiPolygon<T> {
+                self.boolean(other, BooleanOp::Difference)
+            }
+
+            pub fn xor(&self, other: &Self) -> MultiPolygon<T> {
+                self.boolean(other, BooleanOp::Xor)
+            }
+        }
+    };
+}
+
+impl_boolean_ops!(Polygon);
+impl_boolean_ops!(PolygonWithHoles);
+impl_boolean_ops!(MultiPolygon);
+
+fn cmp_points<T: en::Float>(a: &Point<T>, b: &Point<T>) -> Ordering {
+    a.x.partial_cmp(&b.x)
+        .unwrap_or(Ordering::Equal)
+        .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+}
+
+/// Assuming `point` is on the line through `segment`, checks if it's strictly between the
+/// endpoints.
+fn strictly_within<T: en::Float>(segment: LineSegment<T>, point: Point<T>) -> bool {
+    point != segment.from
+        && point != segment.to
+        && (point.x - segment.from.x) * (point.x - segment.to.x) <= T::zero()
+        && (point.y - segment.from.y) * (point.y - segment.to.y) <= T::zero()
+}
+
+/// Records where `a` and `b` need to be split so that they only meet at shared endpoints.
+fn find_splits<T: en::Float>(
+    a: LineSegment<T>,
+    b: LineSegment<T>,
+    splits_a: &mut Vec<Point<T>>,
+    splits_b: &mut Vec<Point<T>>,
+) {
+    let d1 = orient2d(a.from, a.to, b.from);
+    let d2 = orient2d(a.from, a.to, b.to);
+    let d3 = orient2d(b.from, b.to, a.from);
+    let d4 = orient2d(b.from, b.to, a.to);
+    // Multiplying the orientations could underflow, so compare their signs instead.
+    let opposite = |p: f64, q: f64| (p < 0.0 && q > 0.0) || (p > 0.0 && q < 0.0);
+    if d1 == 0.0 && d2 == 0.0 {
+        // The segments are collinear, so any overlap is delimited by their endpoints.
+        splits_a.extend(b.points().filter(|p| strictly_within(a, *p)));
+        splits_b.extend(a.points().filter(|p| strictly_within(b, *p)));
+        return;
+    }
+    if d1 == 0.0 && strictly_within(a, b.from) {
+        splits_a.push(b.from);
+    }
+    if d2 == 0.0 && strictly_within(a, b.to) {
+        splits_a.push(b.to);
+    }
+    if d3 == 0.0 && strictly_within(b, a.from) {
+        splits_b.push(a.from);
+    }
+    if d4 == 0.0 && strictly_within(b, a.to) {
+        splits_b.push(a.to);
+    }
+    if opposite(d1, d2) && opposite(d3, d4) {
+        let crossing = a.from + a.vector() * en::cast::<T, _>(d3 / (d3 - d4));
+        splits_a.push(crossing);
+        splits_b.push(crossing);
+    }
+}
+
+/// An edge between two interned points, remembering which ring it came from.
+#[derive(Clone, Copy, Debug)]
+struct Edge {
+    from: usize,
+    to: usize,
+    ring: usize,
+}
+
+impl Edge {
+    /// Identifies the edge regardless of its direction, so that coincident edges share a key.
+    fn key(&self) -> (usize, usize) {
+        (self.from.min(self.to), self.from.max(self.to))
+    }
+}
+
+/// Splits every edge at every point where it meets another edge, returning the interned points
+/// along with the resulting edges.
+fn split_edges<T: en::Float>(rings: &[&[Point<T>]]) -> (Vec<Point<T>>, Vec<Edge>) {
+    let mut pieces: Vec<_> = rings
+        .iter()
+        .enumerate()
+        .flat_map(|(ring, points)| {
+            Polygon::new(points.to_vec())
+                .line_segments()
+                .filter(|edge| edge.from != edge.to)
+                .map(move |edge| (edge, ring))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    // Crossings get rounded, which can leave the pieces on either side of one still crossing some
+    // other edge, so those are split again (for a few rounds, in case that happens again).
+    for _ in 0..16 {
+        let split;
+        (pieces, split) = split_once(pieces);
+        if !split {
+            break;
+        }
+    }
+
+    let mut points: Vec<_> = pieces
+        .iter()
+        .flat_map(|(piece, _)| [piece.from, piece.to])
+        .collect();
+    points.sort_by(cmp_points);
+    points.dedup();
+    let id = |p: &Point<T>| {
+        points
+            .binary_search_by(|probe| cmp_points(probe, p))
+            .expect("every endpoint was interned")
+    };
+    let edges = pieces
+        .iter()
+        .map(|(piece, ring)| Edge {
+            from: id(&piece.from),
+            to: id(&piece.to),
+            ring: *ring,
+        })
+        .filter(|edge| edge.from != edge.to)
+        .collect();
+    (points, edges)
+}
+
+/// Splits each edge wherever it meets another, and reports if anything was split.
+fn split_once<T: en::Float>(
+    mut edges: Vec<(LineSegment<T>, usize)>,
+) -> (Vec<(LineSegment<T>, usize)>, bool) {
+    edges.sort_by(|(a, _), (b, _)| {
+        let (a, b) = (a.bounding_rect().left(), b.bounding_rect().left());
+        a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+    });
+
+    let mut splits = vec![Vec::new(); edges.len()];
+    for i in 0..edges.len() {
+        let a = edges[i].0;
+        let a_bounds = a.bounding_rect();
+        for (j, (b, _)) in edges.iter().enumerate().skip(i + 1) {
+            let b_bounds = b.bounding_rect();
+            if b_bounds.left() > a_bounds.right() {
+                break;
+            }
+            if b_bounds.bottom() < a_bounds.top() || b_bounds.top() > a_bounds.bottom() {
+                continue;
+            }
+            let (head, tail) = splits.split_at_mut(j);
+            find_splits(a, *b, &mut head[i], &mut tail[0]);
+        }
+    }
+
+    let split = splits.iter().any(|splits| !splits.is_empty());
+    let mut pieces = Vec::new();
+    for ((edge, ring), mut splits) in edges.into_iter().zip(splits) {
+        let along = |p: &Point<T>| (*p - edge.from).dot_product(edge.vector());
+        splits.sort_by(|a, b| along(a).partial_cmp(&along(b)).unwrap_or(Ordering::Equal));
+        let mut points = std::iter::once(edge.from)
+            .chain(splits)
+            .chain(std::iter::once(edge.to))
+            .collect::<Vec<_>>();
+        points.dedup();
+        pieces.extend(
+            points
+                .windows(2)
+                .map(|w| (LineSegment::new(w[0], w[1]), ring)),
+        );
+    }
+    (pieces, split)
+}
+
+/// Computes the region covered by `rings` according to `inside`, which is given the winding number
+/// of each ring around a point and decides if that point is covered.
+///
+/// Every edge is split wherever it touches another, so that the resulting edges only ever meet at
+/// their endpoints or coincide entirely. The winding numbers on either side of each edge then
+/// decide if it's part of the output's boundary, and the boundary edges get chained into rings.
+pub(crate) fn overlay<T: en::Float>(
+    rings: &[&[Point<T>]],
+    inside: impl Fn(&[i32]) -> bool,
+) -> MultiPolygon<T> {
+    let (points, mut edges) = split_edges(rings);
+    edges.sort_by_key(Edge::key);
+    let mut groups = Vec::new();
+    let mut start = 0;
+    while start < edges.len() {
+        let key = edges[start].key();
+        let len = edges[start..]
+            .iter()
+            .take_while(|edge| edge.key() == key)
+            .count();
+        groups.push(&edges[start..start + len]);
+        start += len;
+    }
+    let key = |group: usize| groups[group][0].key();
+    let is_vertical = |group: usize| {
+        let (u, v) = key(group);
+        points[u].x == points[v].x
+    };
+    // Checks if an edge passes below the one from `from` to `to`, just to the right of `from` (or
+    // beside it, if it's vertical). Edges only meet at their endpoints, so `to` only matters if the
+    // other edge passes through `from`.
+    let passes_below = |other: usize, from: Point<T>, to: Point<T>| {
+        let (a, b) = key(other);
+        let side = orient2d(points[a], points[b], from);
+        if side == 0.0 {
+            orient2d(points[a], points[b], to) > 0.0
+        } else {
+            side > 0.0
+        }
+    };
+
+    // Sweeping from left to right, the winding numbers just past each edge in the +y direction are
+    // those just past the edge before it, plus whatever crossing the edge itself adds. Since the
+    // interned points are sorted, each key's first point is its leftmost one.
+    let mut boundary = Vec::new();
+    let mut past = vec![Vec::new(); groups.len()];
+    // The edges that span the current x (apart from vertical ones), sorted by y.
+    let mut active: Vec<usize> = Vec::new();
+    let mut next = 0;
+    while next < groups.len() {
+        let x = points[key(next).0].x;
+        let end = next
+            + (next..groups.len())
+                .take_while(|group| points[key(*group).0].x == x)
+                .count();
+        active.retain(|group| points[key(*group).1].x > x);
+        let (vertical, mut starting): (Vec<_>, Vec<_>) = (next..end).partition(|g| is_vertical(*g));
+        // Edges leaving the same point are ordered by slope, so that each one's neighbor below is
+        // already settled by the time it's inserted.
+        starting.sort_by(|a, b| {
+            let ((u, v), (other, w)) = (key(*a), key(*b));
+            u.cmp(&other).then_with(|| {
+                let side = orient2d(points[u], points[v], points[w]);
+                side.partial_cmp(&0.0).unwrap_or(Ordering::Equal).reverse()
+            })
+        });
+        next = end;
+
+        for group in starting {
+            let (u, v) = key(group);
+            let (from, to) = (points[u], points[v]);
+            let index = active.partition_point(|other| passes_below(*other, from, to));
+            let before = match index.checked_sub(1) {
+                Some(below) => past[active[below]].clone(),
+                None => vec![0; rings.len()],
+            };
+            active.insert(index, group);
+            let probe_is_left = (to - from).cross_product(Vector::from_dy(T::one())) > T::zero();
+            let (left, right) = sides(groups[group], before, !probe_is_left);
+            past[group] = if probe_is_left {
+                left.clone()
+            } else {
+                right.clone()
+            };
+            boundary.extend(boundary_edge(u, v, inside(&left), inside(&right)));
+        }
+
+        for group in vertical {
+            let (u, v) = key(group);
+            let (from, to) = (points[u], points[v]);
+            let index = active.partition_point(|other| passes_below(*other, from, to));
+            let after = match index.checked_sub(1) {
+                Some(below) => past[active[below]].clone(),
+                None => vec![0; rings.len()],
+            };
+            let probe_is_left = (to - from).cross_product(Vector::from_dx(T::one())) > T::zero();
+            let (left, right) = sides(groups[group], after, probe_is_left);
+            boundary.extend(boundary_edge(u, v, inside(&left), inside(&right)));
+        }
+    }
+
+    let rings = chain_rings(&points, &boundary);
+    let (outers, holes): (Vec<_>, Vec<_>) = rings
+        .into_iter()
+        .map(|ring| (ring.signed_area(), ring))
+        .filter(|(area, _)| *area != T::zero())
+        .partition(|(area, _)| *area > T::zero());
+    let mut polygons: Vec<_> = outers
+        .into_iter()
+        .map(|(area, outer)| (area, PolygonWithHoles::from(outer)))
+        .collect();
+    for (_, hole) in holes {
+        let edge = hole.line_segments().next().expect("holes have edges");
+        let probe = edge.from + edge.vector() / T::two();
+        let owner = polygons
+            .iter_mut()
+            .filter(|(_, polygon)| polygon.outer.contains(probe, crate::FillRule::NonZero))
+            .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        if let Some((_, polygon)) = owner {
+            polygon.holes.push(hole);
+        }
+    }
+    polygons.into_iter().map(|(_, polygon)| polygon).collect()
+}
+
+/// Given the winding numbers on one side of a group of coincident edges, finds those on their left
+/// and right. Crossing the edges from right to left adds one for each edge going from the group's
+/// first point to its second, and subtracts one for each going the other way.
+fn sides(group: &[Edge], known: Vec<i32>, known_is_left: bool) -> (Vec<i32>, Vec<i32>) {
+    let first = group[0].key().0;
+    let mut other = known.clone();
+    for edge in group {
+        let jump = if edge.from == first { 1 } else { -1 };
+        other[edge.ring] += if known_is_left { -jump } else { jump };
+    }
+    if known_is_left {
+        (known, other)
+    } else {
+        (other, known)
+    }
+}
+
+/// Keeps the edge from `u` to `v` if it separates covered and uncovered regions, directing it so
+/// that the covered one is on the right.
+fn boundary_edge(u: usize, v: usize, left: bool, right: bool) -> Option<(usize, usize)> {
+    match (left, right) {
+        (true, false) => Some((u, v)),
+        (false, true) => Some((v, u)),
+        _ => None,
+    }
+}
+
+/// Links directed boundary edges into closed rings, keeping the covered region on the right (as
+/// seen on screen). Where several rings meet at a vertex, they're kept separate.
+fn chain_rings<T: en::Float>(points: &[Point<T>], boundary: &[(usize, usize)]) -> Vec<Polygon<T>> {
+    let mut outgoing = vec![Vec::new(); points.len()];
+    for (i, (from, _)) in boundary.iter().enumerate() {
+        outgoing[*from].push(i);
+    }
+    let mut used = vec![false; boundary.len()];
+    let mut rings = Vec::new();
+    for start in 0..boundary.len() {
+        if used[start] {
+            continue;
+        }
+        let mut ring = Vec::new();
+        let mut edge = start;
+        let closed = loop {
+            used[edge] = true;
+            let (from, to) = boundary[edge];
+            ring.push(points[from]);
+            let back = points[from] - points[to];
+            // Take the sharpest turn towards the covered region, so that rings touching at a
+            // vertex don't get merged.
+            let turn = |candidate: &usize| {
+                let out = points[boundary[*candidate].1] - points[to];
+                let angle = -back.cross_product(out).atan2(back.dot_product(out));
+                if angle <= T::zero() {
+                    angle + T::TAU()
+                } else {
+                    angle
+                }
+            };
+            let next = outgoing[to]
+                .iter()
+                .filter(|candidate| !used[**candidate] || **candidate == start)
+                .min_by(|a, b| turn(a).partial_cmp(&turn(b)).unwrap_or(Ordering::Equal));
+            match next {
+                Some(next) if *next == start => break true,
+                Some(next) => edge = *next,
+                None => break false,
+            }
+        };
+        if closed {
+            rings.push(Polygon::new(remove_collinear(ring)));
+        }
+    }
+    rings
+}
+
+/// Removes points that lie on a straight line between their neighbors.
+fn remove_collinear<T: en::Float>(mut ring: Vec<Point<T>>) -> Vec<Point<T>> {
+    let mut i = 0;
+    while ring.len() > 2 && i < ring.len() {
+        let len = ring.len();
+        let prev = ring[(i + len - 1) % len];
+        let next = ring[(i + 1) % len];
+        let (a, b) = (ring[i] - prev, next - ring[i]);
+        if a.cross_product(b) == T::zero() && a.dot_product(b) > T::zero() {
+            ring.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+    ring
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, Angle, Circle, Rect, Size};
+
+    fn square(x: f32, y: f32, size: f32) -> Polygon {
+        Rect::from_top_left(Point::new(x, y), Size::square(size)).into()
+    }
+
+    #[test]
+    fn overlapping() {
+        let a = square(0.0, 0.0, 2.0);
+        let b = square(1.0, 1.0, 2.0);
+        let union = a.union(&b);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union.polygons[0].outer.len(), 8);
+        assert_approx_eq!(union.area(), 7.0);
+        assert_approx_eq!(a.intersection(&b).area(), 1.0);
+        assert_approx_eq!(a.difference(&b).area(), 3.0);
+        let xor = a.xor(&b);
+        assert_eq!(xor.len(), 2);
+        assert_approx_eq!(xor.area(), 6.0);
+        for polygon in &union {
+            assert!(polygon.outer.signed_area() > 0.0);
+        }
+    }
+
+    #[test]
+    fn shared_edge() {
+        let a = square(0.0, 0.0, 1.0);
+        let b = square(1.0, 0.0, 1.0);
+        let union = a.union(&b);
+        assert_eq!(union.len(), 1);
+        assert_eq!(
+            union.polygons[0].outer.bounding_rect(),
+            Rect::from_top_left(Point::zero(), Size::new(2.0, 1.0))
+        );
+        // The points along the shared edge are collinear, so they get dropped.
+        assert_eq!(union.polygons[0].outer.len(), 4);
+        assert!(a.intersection(&b).is_empty());
+        assert_approx_eq!(a.difference(&b).area(), 1.0);
+    }
+
+    #[test]
+    fn touching_vertex() {
+        let a = square(0.0, 0.0, 1.0);
+        let b = square(1.0, 1.0, 1.0);
+        let union = a.union(&b);
+        assert_eq!(union.len(), 2);
+        assert_approx_eq!(union.area(), 2.0);
+        assert!(a.intersection(&b).is_empty());
+    }
+
+    #[test]
+    fn holes() {
+        let frame = square(0.0, 0.0, 10.0).difference(&square(2.0, 2.0, 6.0));
+        assert_eq!(frame.len(), 1);
+        assert_eq!(frame.polygons[0].holes.len(), 1);
+        assert!(frame.polygons[0].holes[0].signed_area() < 0.0);
+        assert_approx_eq!(frame.area(), 64.0);
+        assert!(!frame.contains(Point::new(5.0, 5.0)));
+
+        // Plugging part of the hole leaves a smaller hole behind.
+        let plug = MultiPolygon::from(square(2.0, 2.0, 3.0));
+        let plugged = frame.union(&plug);
+        assert_eq!(plugged.len(), 1);
+        assert_approx_eq!(plugged.area(), 73.0);
+
+        // Cutting out a region straddling the hole's edge.
+        let bite = MultiPolygon::from(square(-1.0, 4.0, 4.0));
+        assert_approx_eq!(frame.difference(&bite).area(), 56.0);
+    }
+
+    #[test]
+    fn containment() {
+        let outer = square(0.0, 0.0, 4.0);
+        let inner = square(1.0, 1.0, 2.0);
+        assert_approx_eq!(outer.union(&inner).area(), 16.0);
+        assert_approx_eq!(outer.intersection(&inner).area(), 4.0);
+        let ring = outer.difference(&inner);
+        assert_eq!(ring.polygons[0].holes.len(), 1);
+        assert!(inner.difference(&outer).is_empty());
+        assert_approx_eq!(inner.xor(&outer).area(), 12.0);
+    }
+
+    #[test]
+    fn non_integer() {
+        let a: Polygon = Polygon::new(vec![
+            Point::new(6.1567316, 7.841396),
+            Point::new(2.2273555, 13.249718),
+            Point::new(-4.130509, 11.183922),
+            Point::new(-4.130507, 4.498867),
+            Point::new(2.2273564, 2.433074),
+        ]);
+        let b: Polygon = Polygon::new(vec![
+            Point::new(6.9434657, 6.6997695),
+            Point::new(5.3344254, 9.48671),
+            Point::new(2.1163447, 9.486709),
+            Point::new(0.50730467, 6.699769),
+            Point::new(2.1163452, 3.9128299),
+            Point::new(5.334425, 3.9128299),
+        ]);
+        let union = a.union(&b).area();
+        let intersection = a.intersection(&b).area();
+        assert!(intersection > 0.0);
+        assert!((union + intersection - a.area() - b.area()).abs() < 0.001);
+        assert!((a.difference(&b).area() + intersection - a.area()).abs() < 0.001);
+        assert!((a.xor(&b).area() + intersection - union).abs() < 0.001);
+    }
+
+    #[test]
+    fn many_edges() {
+        // Checking each edge against every other would make this take minutes instead of moments.
+        let circle = |x: f32| -> Polygon {
+            Circle::new(Point::new(x, 0.3), 100.7)
+                .circle_points(20_000, Angle::from_radians(0.1))
+                .collect()
+        };
+        let (a, b) = (circle(0.1), circle(50.9));
+        let union = a.union(&b);
+        let intersection = a.intersection(&b);
+        assert_eq!(union.len(), 1);
+        assert_eq!(intersection.len(), 1);
+        let error = union.area() + intersection.area() - a.area() - b.area();
+        assert!(error.abs() < 1.0, "{}", error);
+        assert!(a.difference(&b).area() > 0.0);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{Distance, LineSegment, Point, Rect, Vector};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+/// Every point within `radius` of a line segment, which is a rectangle with rounded ends.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Capsule<T = f32> {
+    pub segment: LineSegment<T>,
+    pub radius: T,
+}
+
+impl<T: en::Num> Capsule<T> {
+    pub fn new(segment: LineSegment<T>, radius: T) -> Self {
+        Self { segment, radius }
+    }
+
+    pub fn from_points(from: Point<T>, to: Point<T>, radius: T) -> Self {
+        Self::new(LineSegment::new(from, to), radius)
+    }
+
+    pub fn bounding_rect(&self) -> Rect<T> {
+        self.segment.bounding_rect().outset_uniform(self.radius)
+    }
+
+    pub fn map<U: en::Num>(
+        self,
+        f: impl FnOnce(LineSegment<T>, T) -> (LineSegment<U>, U),
+    ) -> Capsule<U> {
+        let (segment, radius) = f(self.segment, self.radius);
+        Capsule::new(segment, radius)
+    }
+
+    pub fn cast<U: en::Num>(self) -> Capsule<U> {
+        self.map(move |segment, radius| (segment.map(Point::cast), en::cast(radius)))
+    }
+
+    impl_casts!(Capsule);
+}
+
+impl<T: en::Float> Capsule<T> {
+    pub fn contains(&self, point: Point<T>) -> bool {
+        (point - self.segment.closest_point(point)).magnitude_squared() <= self.radius * self.radius
+    }
+}
+
+impl<T: en::Num> Add<Vector<T>> for Capsule<T> {
+    type Output = Self;
+    fn add(self, rhs: Vector<T>) -> Self::Output {
+        Capsule::new(self.segment.map(|point| point + rhs), self.radius)
+    }
+}
+
+impl<T: en::Num> AddAssign<Vector<T>> for Capsule<T> {
+    fn add_assign(&mut self, rhs: Vector<T>) {
+        *self = *self + rhs
+    }
+}
+
+impl<T: en::Num> Sub<Vector<T>> for Capsule<T> {
+    type Output = Self;
+    fn sub(self, rhs: Vector<T>) -> Self::Output {
+        Capsule::new(self.segment.map(|point| point - rhs), self.radius)
+    }
+}
+
+impl<T: en::Num> SubAssign<Vector<T>> for Capsule<T> {
+    fn sub_assign(&mut self, rhs: Vector<T>) {
+        *self = *self - rhs
+    }
+}
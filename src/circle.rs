@@ -1,4 +1,4 @@
-use crate::{Angle, Ellipse, Point, Rect, Vector};
+use crate::{Angle, Ellipse, Point, Polygon, Rect, Vector};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::ops::{Add, AddAssign, Sub, SubAssign};
@@ -99,6 +99,15 @@ impl<T: en::Num> Circle<T> {
         self.to_ellipse().ellipse_points(steps, start_angle)
     }
 
+    /// Approximates the circle with a polygon that strays from it by no more than `tolerance`,
+    /// which should be positive.
+    pub fn flatten(&self, tolerance: T) -> Polygon<T>
+    where
+        T: en::Float,
+    {
+        self.to_ellipse().flatten(tolerance)
+    }
+
     pub fn map<U: en::Num>(self, f: impl FnOnce(Point<T>, T) -> (Point<U>, U)) -> Circle<U> {
         let (center, radius) = f(self.center, self.radius);
         Circle::new(center, radius)
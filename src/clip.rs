@@ -0,0 +1,192 @@
+use crate::{Distance, LineSegment, Point, Polyline, Ray, Rect, Vector};
+
+/// Finds the range of `t` in `0..=max` where `origin + vector * t` is inside `rect` (including its
+/// edges), using the Liang-Barsky algorithm.
+pub(crate) fn liang_barsky<T: en::Float>(
+    origin: Point<T>,
+    vector: Vector<T>,
+    max: T,
+    rect: &Rect<T>,
+) -> Option<(T, T)> {
+    let (mut start, mut end) = (T::zero(), max);
+    for (p, q) in [
+        (-vector.dx, origin.x - rect.left()),
+        (vector.dx, rect.right() - origin.x),
+        (-vector.dy, origin.y - rect.top()),
+        (vector.dy, rect.bottom() - origin.y),
+    ] {
+        if p == T::zero() {
+            // Parallel to this edge, so it's either entirely inside or entirely outside of it.
+            if q < T::zero() {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if p < T::zero() {
+                if t > end {
+                    return None;
+                } else if t > start {
+                    start = t;
+                }
+            } else if t < start {
+                return None;
+            } else if t < end {
+                end = t;
+            }
+        }
+    }
+    Some((start, end))
+}
+
+impl<T: en::Float> LineSegment<T> {
+    /// Returns the part of the segment that's inside `rect` (including its edges), or `None` if
+    /// they don't meet. Endpoints that are already inside are kept exactly.
+    pub fn clip_to_rect(&self, rect: &Rect<T>) -> Option<LineSegment<T>> {
+        let vector = self.vector();
+        let (start, end) = liang_barsky(self.from, vector, T::one(), rect)?;
+        // Rounding can leave points just outside of `rect`, so pull them back onto its edge.
+        let at = |t: T| rect.closest_point(self.from + vector * t);
+        Some(LineSegment::new(
+            if start == T::zero() {
+                self.from
+            } else {
+                at(start)
+            },
+            if end == T::one() { self.to } else { at(end) },
+        ))
+    }
+}
+
+impl<T: en::Float> Ray<T> {
+    /// Returns the part of the ray that's inside `rect` (including its edges), or `None` if they
+    /// don't meet.
+    pub fn clip_to_rect(&self, rect: &Rect<T>) -> Option<LineSegment<T>> {
+        let vector = self.unit_vector();
+        let (start, end) = liang_barsky(self.point, vector, T::infinity(), rect)?;
+        let at = |t: T| rect.closest_point(self.point + vector * t);
+        Some(LineSegment::new(
+            if start == T::zero() {
+                self.point
+            } else {
+                at(start)
+            },
+            at(end),
+        ))
+    }
+}
+
+impl<T: en::Float> Polyline<T> {
+    /// Returns the parts of the polyline that are inside `rect` (including its edges). The polyline
+    /// is split wherever it leaves the rect, so this returns one piece per visit.
+    pub fn clip_to_rect(&self, rect: &Rect<T>) -> Vec<Polyline<T>> {
+        let mut pieces = Vec::new();
+        let mut current: Vec<Point<T>> = Vec::new();
+        let mut finish = |current: &mut Vec<Point<T>>| {
+            if current.len() >= 2 {
+                pieces.push(Polyline::new(std::mem::take(current)));
+            } else {
+                current.clear();
+            }
+        };
+        for segment in self.line_segments() {
+            match segment.clip_to_rect(rect) {
+                Some(clipped) => {
+                    if current.last() != Some(&clipped.from) {
+                        finish(&mut current);
+                        current.push(clipped.from);
+                    }
+                    current.push(clipped.to);
+                    if clipped.to != segment.to {
+                        finish(&mut current);
+                    }
+                }
+                None => finish(&mut current),
+            }
+        }
+        finish(&mut current);
+        pieces
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, test::segment, Angle, Size};
+
+    fn viewport() -> Rect {
+        Rect::from_top_left(Point::zero(), Size::new(4.0, 2.0))
+    }
+
+    #[test]
+    fn line_segments() {
+        let rect = viewport();
+        assert_eq!(
+            segment(-2.0, 1.0, 6.0, 1.0).clip_to_rect(&rect),
+            Some(segment(0.0, 1.0, 4.0, 1.0))
+        );
+        assert_eq!(
+            segment(1.0, 1.0, 3.0, 0.5).clip_to_rect(&rect),
+            Some(segment(1.0, 1.0, 3.0, 0.5))
+        );
+        let clipped = segment(-1.0, -1.0, 5.0, 5.0).clip_to_rect(&rect).unwrap();
+        assert_approx_eq!(clipped.from.x, 0.0);
+        assert_approx_eq!(clipped.from.y, 0.0);
+        assert_approx_eq!(clipped.to.x, 2.0);
+        assert_eq!(clipped.to.y, 2.0);
+        assert_eq!(segment(5.0, -1.0, 5.0, 3.0).clip_to_rect(&rect), None);
+        assert_eq!(segment(-1.0, 1.0, 0.0, 3.0).clip_to_rect(&rect), None);
+        // Running along an edge counts as inside.
+        assert_eq!(
+            segment(-1.0, 0.0, 1.0, 0.0).clip_to_rect(&rect),
+            Some(segment(0.0, 0.0, 1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn rays() {
+        let rect = viewport();
+        let clipped = Ray::new(Point::new(-2.0, 1.0), Angle::ZERO())
+            .clip_to_rect(&rect)
+            .unwrap();
+        assert_approx_eq!(clipped.from.x, 0.0);
+        assert_approx_eq!(clipped.to.x, 4.0);
+        assert_approx_eq!(clipped.to.y, 1.0);
+        let inside = Ray::new(Point::new(1.0, 1.0), Angle::FRAC_PI_2())
+            .clip_to_rect(&rect)
+            .unwrap();
+        assert_eq!(inside.from, Point::new(1.0, 1.0));
+        // Angles run counter-clockwise as seen on screen, so this points up.
+        assert_approx_eq!(inside.to.x, 1.0);
+        assert_approx_eq!(inside.to.y, 0.0);
+        assert_eq!(
+            Ray::new(Point::new(-2.0, 1.0), Angle::PI()).clip_to_rect(&rect),
+            None
+        );
+    }
+
+    #[test]
+    fn polylines() {
+        let rect = viewport();
+        // Weaves in and out of the top edge.
+        let polyline = Polyline::new(vec![
+            Point::new(-1.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.5, -1.0),
+            Point::new(2.5, -1.0),
+            Point::new(3.0, 1.0),
+            Point::new(3.5, 1.0),
+        ]);
+        let pieces = polyline.clip_to_rect(&rect);
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].points().len(), 3);
+        assert_eq!(pieces[0].points()[0], Point::new(0.0, 1.0));
+        assert_eq!(pieces[0].points()[1], Point::new(1.0, 1.0));
+        assert_approx_eq!(pieces[0].points()[2].x, 1.25);
+        assert_eq!(pieces[0].points()[2].y, 0.0);
+        assert_eq!(pieces[1].points().len(), 3);
+        assert_approx_eq!(pieces[1].points()[0].x, 2.75);
+        assert_eq!(pieces[1].points()[2], Point::new(3.5, 1.0));
+        let elsewhere = Rect::from_top_left(Point::new(10.0, 10.0), Size::new(1.0, 1.0));
+        assert!(polyline.clip_to_rect(&elsewhere).is_empty());
+    }
+}
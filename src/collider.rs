@@ -0,0 +1,308 @@
+use crate::{Circle, Ellipse, Point, Polygon, Quad, Rect, Vector};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How far two overlapping shapes need to move apart to stop overlapping.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Penetration<T = f32> {
+    /// The unit vector pointing from the first shape towards the second.
+    pub normal: Vector<T>,
+    /// How far the shapes overlap along `normal`.
+    pub depth: T,
+}
+
+impl<T: en::Float> Penetration<T> {
+    /// Returns the minimum translation vector, which separates the shapes when added to the second
+    /// shape (or subtracted from the first).
+    pub fn translation(&self) -> Vector<T> {
+        self.normal * self.depth
+    }
+
+    /// Returns the same penetration from the perspective of the other shape.
+    pub fn reverse(self) -> Self {
+        Self {
+            normal: -self.normal,
+            depth: self.depth,
+        }
+    }
+}
+
+/// A convex shape that can be tested for overlap using the separating axis theorem, which states
+/// that two convex shapes don't overlap if and only if there's an axis along which their
+/// projections don't overlap.
+pub trait Collider<T: en::Float> {
+    /// Returns the smallest and largest values of `point.dot_product(axis)` over every point in the
+    /// shape.
+    fn project(&self, axis: Vector<T>) -> (T, T);
+
+    /// Returns the points that another shape's axes should be aimed at, such as the vertices of a
+    /// polygon or the center of a circle.
+    fn features(&self) -> Vec<Point<T>>;
+
+    /// Adds every axis that could separate this shape from another shape that has the given
+    /// `features`. The axes don't need to be normalized, but they can't be zero.
+    fn axes(&self, features: &[Point<T>], axes: &mut Vec<Vector<T>>);
+
+    /// Checks if the shapes overlap. Shapes that only touch aren't considered overlapping.
+    fn overlaps(&self, other: &impl Collider<T>) -> bool
+    where
+        Self: Sized,
+    {
+        self.penetration(other).is_some()
+    }
+
+    /// Returns the smallest movement that separates the shapes, or `None` if they don't overlap.
+    fn penetration(&self, other: &impl Collider<T>) -> Option<Penetration<T>>
+    where
+        Self: Sized,
+    {
+        let mut axes = Vec::new();
+        self.axes(&other.features(), &mut axes);
+        other.axes(&self.features(), &mut axes);
+        let mut best: Option<Penetration<T>> = None;
+        for axis in axes {
+            let axis = axis.normalize();
+            let (min, max) = self.project(axis);
+            let (other_min, other_max) = other.project(axis);
+            // Either push the other shape forward along the axis, or push it backward.
+            let forward = max - other_min;
+            let backward = other_max - min;
+            let penetration = if forward <= backward {
+                Penetration {
+                    normal: axis,
+                    depth: forward,
+                }
+            } else {
+                Penetration {
+                    normal: -axis,
+                    depth: backward,
+                }
+            };
+            if penetration.depth <= T::zero() {
+                return None;
+            }
+            match best {
+                Some(best) if best.depth <= penetration.depth => (),
+                _ => best = Some(penetration),
+            }
+        }
+        best
+    }
+}
+
+fn project_points<T: en::Float>(
+    points: impl IntoIterator<Item = Point<T>>,
+    axis: Vector<T>,
+) -> (T, T) {
+    points
+        .into_iter()
+        .map(|point| point.to_vector().dot_product(axis))
+        .fold((T::infinity(), T::neg_infinity()), |(min, max), value| {
+            (en::Num::min(min, value), en::Num::max(max, value))
+        })
+}
+
+fn edge_normals<T: en::Float>(points: &[Point<T>], axes: &mut Vec<Vector<T>>) {
+    let next = points.iter().cycle().skip(1);
+    axes.extend(
+        points
+            .iter()
+            .zip(next)
+            .map(|(from, to)| *to - *from)
+            .filter(|edge| *edge != Vector::zero())
+            .map(|edge| Vector::new(edge.dy, -edge.dx)),
+    );
+}
+
+impl<T: en::Float> Collider<T> for Rect<T> {
+    fn project(&self, axis: Vector<T>) -> (T, T) {
+        project_points(self.clockwise_points(), axis)
+    }
+
+    fn features(&self) -> Vec<Point<T>> {
+        self.clockwise_points().collect()
+    }
+
+    fn axes(&self, _features: &[Point<T>], axes: &mut Vec<Vector<T>>) {
+        axes.extend([Vector::from_dx(T::one()), Vector::from_dy(T::one())]);
+    }
+}
+
+impl<T: en::Float> Collider<T> for Quad<T> {
+    fn project(&self, axis: Vector<T>) -> (T, T) {
+        project_points(self.features(), axis)
+    }
+
+    fn features(&self) -> Vec<Point<T>> {
+        vec![self.a, self.b, self.c, self.d]
+    }
+
+    fn axes(&self, _features: &[Point<T>], axes: &mut Vec<Vector<T>>) {
+        edge_normals(&self.features(), axes);
+    }
+}
+
+/// Non-convex polygons collide like their convex hull.
+impl<T: en::Float> Collider<T> for Polygon<T> {
+    fn project(&self, axis: Vector<T>) -> (T, T) {
+        project_points(self.points().iter().copied(), axis)
+    }
+
+    fn features(&self) -> Vec<Point<T>> {
+        self.points().to_vec()
+    }
+
+    fn axes(&self, _features: &[Point<T>], axes: &mut Vec<Vector<T>>) {
+        if self.is_convex() {
+            edge_normals(self.points(), axes);
+        } else {
+            edge_normals(self.convex_hull().points(), axes);
+        }
+    }
+}
+
+impl<T: en::Float> Collider<T> for Circle<T> {
+    fn project(&self, axis: Vector<T>) -> (T, T) {
+        let center = self.center().to_vector().dot_product(axis);
+        (center - self.radius(), center + self.radius())
+    }
+
+    fn features(&self) -> Vec<Point<T>> {
+        vec![self.center()]
+    }
+
+    fn axes(&self, features: &[Point<T>], axes: &mut Vec<Vector<T>>) {
+        // A feature at the center doesn't point anywhere, but since every direction is the same
+        // to a circle, any axis will do.
+        axes.extend(features.iter().map(|feature| {
+            let axis = *feature - self.center();
+            if axis == Vector::zero() {
+                Vector::from_dx(T::one())
+            } else {
+                axis
+            }
+        }));
+    }
+}
+
+/// For any pair that doesn't involve two ellipses, the axes are exact. Two ellipses have no finite
+/// set of axes that's guaranteed to include the best one, so they also try a fan of 32 evenly
+/// spaced axes, which keeps the depth within a fraction of a percent in practice.
+impl<T: en::Float> Collider<T> for Ellipse<T> {
+    fn project(&self, axis: Vector<T>) -> (T, T) {
+        let center = self.center().to_vector().dot_product(axis);
+        let radius = self.radius();
+        let extent = (axis.dx * radius.width()).hypot(axis.dy * radius.height());
+        (center - extent, center + extent)
+    }
+
+    fn features(&self) -> Vec<Point<T>> {
+        vec![self.center()]
+    }
+
+    fn axes(&self, features: &[Point<T>], axes: &mut Vec<Vector<T>>) {
+        // The best axis towards a point is always perpendicular to the ellipse at the spot that's
+        // closest to that point.
+        axes.extend(
+            features
+                .iter()
+                .map(|feature| self.outline_normal(self.closest_outline_point(*feature)))
+                .filter(|axis| axis.dx.is_finite() && axis.dy.is_finite()),
+        );
+        let count = 32;
+        axes.extend((0..count).map(|index| {
+            let angle = T::PI() * en::cast(index) / en::cast(count);
+            Vector::new(angle.cos(), angle.sin())
+        }));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, test::rect, Angle, Size, Transform};
+
+    #[test]
+    fn rects() {
+        let a = rect(0.0, 0.0, 4.0, 4.0);
+        let b = rect(3.0, 1.0, 4.0, 4.0);
+        let penetration = a.penetration(&b).unwrap();
+        assert_eq!(penetration.normal, Vector::new(1.0, 0.0));
+        assert_approx_eq!(penetration.depth, 1.0);
+        assert_eq!(b.penetration(&a), Some(penetration.reverse()));
+        // Touching isn't overlapping.
+        assert!(!a.overlaps(&rect(4.0, 0.0, 1.0, 1.0)));
+        assert!(!a.overlaps(&rect(5.0, 0.0, 1.0, 1.0)));
+    }
+
+    #[test]
+    fn rotated_quad() {
+        let a = rect(0.0, 0.0, 2.0, 2.0);
+        let diamond = rect(-1.0, -1.0, 2.0, 2.0)
+            .transform(Transform::from_rotation(Angle::FRAC_PI_4(), Point::zero()));
+        // The diamond's bounding box overlaps this rect, but the diamond itself doesn't reach it.
+        let far = rect(1.0, -2.0, 2.0, 1.0);
+        assert!(diamond.aabb().intersection(&far).is_some());
+        assert!(!diamond.overlaps(&far));
+        let penetration = a.penetration(&diamond).unwrap();
+        assert_approx_eq!(penetration.depth, 1.0);
+        let offset = penetration.translation();
+        let translated = Quad {
+            a: diamond.a + offset,
+            b: diamond.b + offset,
+            c: diamond.c + offset,
+            d: diamond.d + offset,
+        };
+        assert!(!a.overlaps(&translated));
+    }
+
+    #[test]
+    fn circles() {
+        let a = Circle::new(Point::zero(), 2.0);
+        let b = Circle::new(Point::new(3.0, 4.0), 4.0);
+        let penetration = a.penetration(&b).unwrap();
+        assert_approx_eq!(penetration.depth, 1.0);
+        assert_approx_eq!(penetration.normal.dx, 0.6);
+        assert_approx_eq!(penetration.normal.dy, 0.8);
+
+        // The circle is nearest the rect's corner.
+        let corner = Circle::new(Point::new(5.0, 5.0), 2.0);
+        let penetration = rect(0.0, 0.0, 4.0, 4.0).penetration(&corner).unwrap();
+        assert_approx_eq!(penetration.depth, 2.0 - 2f32.sqrt());
+        assert_approx_eq!(penetration.normal.dx, penetration.normal.dy);
+        assert!(!Circle::new(Point::new(5.5, 5.5), 2.0).overlaps(&rect(0.0, 0.0, 4.0, 4.0)));
+
+        // Circles with the same center still overlap.
+        let inner = Circle::new(Point::new(1.0, 1.0), 1.0);
+        let penetration = Circle::new(Point::new(1.0, 1.0), 2.0)
+            .penetration(&inner)
+            .unwrap();
+        assert_approx_eq!(penetration.depth, 3.0);
+        assert_approx_eq!(penetration.normal.magnitude(), 1.0);
+    }
+
+    #[test]
+    fn polygons() {
+        let triangle = Polygon::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(0.0, 4.0),
+        ]);
+        assert!(triangle.overlaps(&rect(1.0, 1.0, 1.0, 1.0)));
+        assert!(!triangle.overlaps(&rect(2.5, 2.5, 1.0, 1.0)));
+        let penetration = triangle.penetration(&rect(1.5, 1.5, 2.0, 2.0)).unwrap();
+        assert_approx_eq!(penetration.depth, 0.5f32.sqrt());
+
+        let ellipse = Ellipse::new(Point::new(6.0, 0.0), Size::new(3.0, 1.0));
+        let penetration = rect(0.0, -2.0, 4.0, 4.0).penetration(&ellipse).unwrap();
+        assert_eq!(penetration.normal, Vector::new(1.0, 0.0));
+        assert_approx_eq!(penetration.depth, 1.0);
+        let circle = Circle::new(Point::new(0.0, 2.5), 1.0);
+        let penetration = Ellipse::new(Point::zero(), Size::new(3.0, 2.0))
+            .penetration(&circle)
+            .unwrap();
+        assert_approx_eq!(penetration.depth, 0.5);
+    }
+}
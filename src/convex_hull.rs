@@ -0,0 +1,243 @@
+use crate::{orient2d, Point, Polygon, Quad, Vector};
+use std::{borrow::Borrow, cmp::Ordering};
+
+/// Returns how far `b` turns from the direction of `a - origin`, which is positive for clockwise
+/// turns (as seen on screen).
+fn turn<T: en::Float>(origin: Point<T>, a: Point<T>, b: Point<T>) -> T {
+    (a - origin).cross_product(b - origin)
+}
+
+impl<T: en::Float> Polygon<T> {
+    /// Builds the smallest convex polygon containing every point, using Andrew's monotone chain
+    /// algorithm. The hull is wound clockwise and doesn't include collinear points. If every point
+    /// lies on one line, the hull is just the two extreme points.
+    pub fn from_convex_hull<I>(points: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Point<T>>,
+    {
+        let mut points: Vec<_> = points.into_iter().map(|point| *point.borrow()).collect();
+        points.sort_by(|a, b| {
+            a.x.partial_cmp(&b.x)
+                .unwrap_or(Ordering::Equal)
+                .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+        });
+        points.dedup();
+        if points.len() < 3 {
+            return Self::new(points);
+        }
+        // The first pass builds the chain along the top, and the second pass builds the chain back
+        // along the bottom. Each pass discards points that don't turn clockwise.
+        let mut hull: Vec<Point<T>> = Vec::with_capacity(points.len() + 1);
+        for point in &points {
+            while hull.len() >= 2
+                && orient2d(hull[hull.len() - 2], hull[hull.len() - 1], *point) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(*point);
+        }
+        let floor = hull.len() + 1;
+        for point in points.iter().rev().skip(1) {
+            while hull.len() >= floor
+                && orient2d(hull[hull.len() - 2], hull[hull.len() - 1], *point) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(*point);
+        }
+        // The bottom chain ends back at the first point.
+        hull.pop();
+        Self::new(hull)
+    }
+
+    /// Returns the convex hull of the polygon's points.
+    pub fn convex_hull(&self) -> Self {
+        Self::from_convex_hull(self.points())
+    }
+
+    /// Returns the greatest distance between any two of the polygon's points.
+    pub fn diameter(&self) -> T {
+        let hull = self.convex_hull();
+        let points = hull.points();
+        let len = points.len();
+        let distance = |a: usize, b: usize| (points[a % len] - points[b % len]).magnitude();
+        match len {
+            0 | 1 => T::zero(),
+            2 => distance(0, 1),
+            _ => {
+                // For each edge, find the point farthest from it; the diameter is always between
+                // an edge's endpoint and such an antipodal point. As the edges rotate around the
+                // hull, the antipodal point rotates along with them.
+                let mut antipodal = 1;
+                let mut diameter = T::zero();
+                for i in 0..len {
+                    let (a, b) = (points[i], points[(i + 1) % len]);
+                    while turn(a, b, points[(antipodal + 1) % len])
+                        > turn(a, b, points[antipodal % len])
+                    {
+                        antipodal += 1;
+                    }
+                    for distance in [distance(i, antipodal), distance(i + 1, antipodal)] {
+                        if distance > diameter {
+                            diameter = distance;
+                        }
+                    }
+                }
+                diameter
+            }
+        }
+    }
+
+    /// Returns the smallest distance between two parallel lines that enclose the polygon.
+    pub fn width(&self) -> T {
+        let hull = self.convex_hull();
+        let points = hull.points();
+        let len = points.len();
+        if len < 3 {
+            return T::zero();
+        }
+        // The narrowest pair of parallel lines always has one line flush with an edge, so this
+        // checks how far the antipodal point of each edge is from it.
+        let mut antipodal = 1;
+        let mut width = T::infinity();
+        for i in 0..len {
+            let (a, b) = (points[i], points[(i + 1) % len]);
+            while turn(a, b, points[(antipodal + 1) % len]) > turn(a, b, points[antipodal % len]) {
+                antipodal += 1;
+            }
+            let distance = turn(a, b, points[antipodal % len]) / (b - a).magnitude();
+            if distance < width {
+                width = distance;
+            }
+        }
+        width
+    }
+
+    /// Returns the smallest rectangle of any orientation that contains the polygon, as a `Quad`
+    /// wound clockwise. One of its sides is always flush with an edge of the convex hull.
+    pub fn oriented_bounding_box(&self) -> Quad<T> {
+        let hull = self.convex_hull();
+        let points = hull.points();
+        let len = points.len();
+        if len < 2 {
+            let point = points.first().copied().unwrap_or_else(Point::zero);
+            return Quad {
+                a: point,
+                b: point,
+                c: point,
+                d: point,
+            };
+        }
+        // Three calipers track the points that are farthest forward, farthest back, and farthest
+        // inward relative to the current edge. All of them only ever move forward around the hull.
+        let (mut forward, mut inward, mut backward) = (1, 1, 1);
+        let mut best_area = T::infinity();
+        let mut best = Quad::default();
+        for i in 0..len {
+            let origin = points[i];
+            let along = (points[(i + 1) % len] - origin).normalize();
+            let across = Vector::new(-along.dy, along.dx);
+            let project =
+                |index: usize, axis: Vector<T>| (points[index % len] - origin).dot_product(axis);
+            while project(forward + 1, along) > project(forward, along) {
+                forward += 1;
+            }
+            inward = inward.max(forward);
+            while project(inward + 1, across) > project(inward, across) {
+                inward += 1;
+            }
+            backward = backward.max(inward);
+            while project(backward + 1, along) < project(backward, along) {
+                backward += 1;
+            }
+            let (min, max) = (project(backward, along), project(forward, along));
+            let depth = project(inward, across);
+            let area = (max - min) * depth;
+            if area < best_area {
+                let a = origin + along * min;
+                let b = origin + along * max;
+                best_area = area;
+                best = Quad {
+                    a,
+                    b,
+                    c: b + across * depth,
+                    d: a + across * depth,
+                };
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, Angle, Rect, Size, Transform, Winding};
+
+    #[test]
+    fn hull() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(4.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(4.0, 4.0),
+            Point::new(1.0, 2.0),
+            Point::new(0.0, 4.0),
+            Point::new(0.0, 2.0),
+            Point::new(4.0, 4.0),
+        ];
+        let hull = Polygon::from_convex_hull(points);
+        assert_eq!(hull.len(), 4);
+        assert_eq!(hull.winding(), Some(Winding::Clockwise));
+        assert_approx_eq!(hull.area(), 16.0);
+        assert!(hull.is_convex());
+
+        let line = Polygon::from_convex_hull(vec![
+            Point::new(1.0, 1.0),
+            Point::new(3.0, 3.0),
+            Point::new(2.0, 2.0),
+        ]);
+        assert_eq!(line.points(), &[Point::new(1.0, 1.0), Point::new(3.0, 3.0)]);
+        assert!(Polygon::<f32>::from_convex_hull(Vec::<Point>::new()).is_empty());
+    }
+
+    #[test]
+    fn calipers() {
+        let rect = Polygon::from(Rect::from_top_left(Point::zero(), Size::new(4.0, 3.0)));
+        assert_approx_eq!(rect.diameter(), 5.0);
+        assert_approx_eq!(rect.width(), 3.0);
+
+        let triangle = Polygon::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(2.0, 3.0),
+        ]);
+        assert_approx_eq!(triangle.diameter(), 4.0);
+        assert_approx_eq!(triangle.width(), 3.0);
+    }
+
+    #[test]
+    fn oriented_bounding_box() {
+        let rect = Rect::from_top_left(Point::new(1.0, 2.0), Size::new(4.0, 2.0));
+        let rotated = rect.transform(Transform::from_rotation(
+            Angle::from_degrees(30.0),
+            rect.center(),
+        ));
+        let obb = Polygon::from(rotated).oriented_bounding_box();
+        let obb = Polygon::from(obb);
+        assert_eq!(obb.winding(), Some(Winding::Clockwise));
+        assert_approx_eq!(obb.area(), 8.0);
+        assert!(rotated.aabb().width() * rotated.aabb().height() > 8.5);
+
+        let diamond = Polygon::new(vec![
+            Point::new(0.0, -1.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 1.0),
+            Point::new(-1.0, 0.0),
+            Point::new(0.0, 0.5),
+        ]);
+        assert_approx_eq!(Polygon::from(diamond.oriented_bounding_box()).area(), 2.0);
+    }
+}
@@ -0,0 +1,500 @@
+use crate::{Intersection, LineSegment, Point};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Borrow,
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
+/// A point where two or more segments meet, whether they cross or just touch.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Crossing<T = f32> {
+    pub point: Point<T>,
+    /// The indices of the segments that meet here, in ascending order.
+    pub segments: Vec<usize>,
+}
+
+impl<T: en::Float> LineSegment<T> {
+    /// Finds every point where two or more of the segments meet, using the Bentley-Ottmann sweep
+    /// line algorithm, which takes `O((n + k) log n)` time for `n` segments meeting at `k` points.
+    /// Collinear segments that overlap meet at the ends of the overlap.
+    ///
+    /// The crossings are ordered from top to bottom, and then from left to right.
+    pub fn crossings<I>(segments: I) -> Vec<Crossing<T>>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<LineSegment<T>>,
+    {
+        // Rounding error in f32 is coarse enough that crossings can land out of order along nearly
+        // horizontal segments, so the sweep always works in f64.
+        Sweep::new(
+            segments
+                .into_iter()
+                .map(|segment| segment.borrow().map(Point::to_f64)),
+        )
+        .run()
+        .into_iter()
+        .map(|crossing| Crossing {
+            point: crossing.point.cast(),
+            segments: crossing.segments,
+        })
+        .collect()
+    }
+}
+
+/// Compares points in the order that the sweep line reaches them.
+fn sweep_order(a: Point<f64>, b: Point<f64>) -> Ordering {
+    a.y.partial_cmp(&b.y)
+        .unwrap_or(Ordering::Equal)
+        .then(a.x.partial_cmp(&b.x).unwrap_or(Ordering::Equal))
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Event(Point<f64>);
+
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Event {}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> Ordering {
+        sweep_order(self.0, other.0)
+    }
+}
+
+const NONE: usize = usize::MAX;
+
+/// A node in the treap holding the segments that cross the sweep line, ordered from left to right.
+/// The order depends on where the sweep line is, so rather than comparing keys, the treap is split
+/// apart by position whenever something needs to be inserted or removed.
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    segment: usize,
+    priority: u32,
+    left: usize,
+    right: usize,
+}
+
+struct Sweep {
+    /// Each segment, flipped if needed so that `from` is reached by the sweep line first.
+    segments: Vec<LineSegment<f64>>,
+    /// Segment indices sorted by their `from` points.
+    starts: Vec<usize>,
+    events: BinaryHeap<Reverse<Event>>,
+    nodes: Vec<Node>,
+    seed: u32,
+    /// Points closer together than this are treated as the same point.
+    tolerance: f64,
+}
+
+impl Sweep {
+    fn new(segments: impl Iterator<Item = LineSegment<f64>>) -> Self {
+        let segments: Vec<_> = segments
+            .map(|segment| {
+                if sweep_order(segment.from, segment.to) == Ordering::Greater {
+                    LineSegment::new(segment.to, segment.from)
+                } else {
+                    segment
+                }
+            })
+            .collect();
+        let mut starts: Vec<usize> = (0..segments.len()).collect();
+        starts.sort_by(|a, b| sweep_order(segments[*a].from, segments[*b].from));
+        let events = segments
+            .iter()
+            .map(|segment| Reverse(Event(segment.to)))
+            .collect();
+        let scale = segments
+            .iter()
+            .flat_map(|segment| segment.points())
+            .fold(1.0, |scale: f64, point| {
+                scale.max(point.x.abs()).max(point.y.abs())
+            });
+        Self {
+            segments,
+            starts,
+            events,
+            nodes: Vec::new(),
+            seed: 0x2545_f491,
+            tolerance: scale * f64::EPSILON * 16.0,
+        }
+    }
+
+    fn run(mut self) -> Vec<Crossing<f64>> {
+        let mut crossings = Vec::new();
+        let mut root = NONE;
+        let mut next_start = 0;
+        loop {
+            // The next event is either the next segment to start, or the next end or crossing.
+            let start = self
+                .starts
+                .get(next_start)
+                .map(|index| self.segments[*index].from);
+            let queued = self.events.peek().map(|event| event.0 .0);
+            let point = match (start, queued) {
+                (Some(start), Some(queued)) => {
+                    if sweep_order(start, queued) == Ordering::Greater {
+                        queued
+                    } else {
+                        start
+                    }
+                }
+                (Some(point), None) | (None, Some(point)) => point,
+                (None, None) => break,
+            };
+            while matches!(self.events.peek(), Some(event) if self.near(event.0 .0, point)) {
+                self.events.pop();
+            }
+            let mut starting = Vec::new();
+            while let Some(index) = self.starts.get(next_start).copied() {
+                if !self.near(self.segments[index].from, point) {
+                    break;
+                }
+                starting.push(index);
+                next_start += 1;
+            }
+            root = self.handle(root, point, starting, &mut crossings);
+        }
+        crossings
+    }
+
+    fn near(&self, a: Point<f64>, b: Point<f64>) -> bool {
+        (a.x - b.x).abs() <= self.tolerance && (a.y - b.y).abs() <= self.tolerance
+    }
+
+    /// Returns whether a segment is left of `point`, passes through it, or is right of it.
+    fn side(&self, segment: usize, point: Point<f64>) -> Ordering {
+        let segment = self.segments[segment];
+        let min = segment.from.x.min(segment.to.x);
+        let max = segment.from.x.max(segment.to.x);
+        // Nearly horizontal segments pass close to points far beyond their ends.
+        if point.x > max + self.tolerance {
+            return Ordering::Less;
+        }
+        if point.x < min - self.tolerance {
+            return Ordering::Greater;
+        }
+        let vector = segment.vector();
+        let distance = vector.cross_product(point - segment.from) / vector.magnitude();
+        if distance < -self.tolerance {
+            Ordering::Less
+        } else if distance > self.tolerance {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    fn handle(
+        &mut self,
+        root: usize,
+        point: Point<f64>,
+        starting: Vec<usize>,
+        crossings: &mut Vec<Crossing<f64>>,
+    ) -> usize {
+        // Pull out every segment passing through the point.
+        let (mut left, rest) = self.split(root, &|sweep, segment| {
+            sweep.side(segment, point) == Ordering::Less
+        });
+        let (middle, mut right) = self.split(rest, &|sweep, segment| {
+            sweep.side(segment, point) != Ordering::Greater
+        });
+        let mut through = Vec::new();
+        self.collect(middle, &mut through);
+        // Rounding error can leave a neighbor that crosses one of these segments here just outside
+        // the tolerance, so pull those in too.
+        loop {
+            if let Some(segment) = self.last(left) {
+                if through
+                    .iter()
+                    .chain(&starting)
+                    .any(|other| self.crossed(segment, *other, point))
+                {
+                    left = self.pop_last(left).0;
+                    through.insert(0, segment);
+                    continue;
+                }
+            }
+            if let Some(segment) = self.first(right) {
+                if through
+                    .iter()
+                    .chain(&starting)
+                    .any(|other| self.crossed(*other, segment, point))
+                {
+                    right = self.pop_first(right).0;
+                    through.push(segment);
+                    continue;
+                }
+            }
+            let ending = |segment: &usize| self.near(self.segments[*segment].to, point);
+            if through.iter().chain(&starting).all(ending) {
+                if let (Some(a), Some(b)) = (self.last(left), self.first(right)) {
+                    if self.crossed(a, b, point) {
+                        left = self.pop_last(left).0;
+                        right = self.pop_first(right).0;
+                        through.insert(0, a);
+                        through.push(b);
+                        continue;
+                    }
+                }
+            }
+            break;
+        }
+        let mut meeting: Vec<usize> = through.iter().chain(&starting).copied().collect();
+        meeting.sort_unstable();
+        meeting.dedup();
+        if meeting.len() > 1 {
+            crossings.push(Crossing {
+                point,
+                segments: meeting,
+            });
+        }
+        // Put back everything that continues below the point, in the order that they leave it.
+        let mut continuing: Vec<usize> = through
+            .into_iter()
+            .chain(starting)
+            .filter(|segment| !self.near(self.segments[*segment].to, point))
+            .collect();
+        continuing.sort_by(|a, b| self.order_below(*a, *b));
+        let middle = continuing.iter().fold(NONE, |middle, segment| {
+            let node = self.node(*segment);
+            self.merge(middle, node)
+        });
+        let (left_neighbor, right_neighbor) = (self.last(left), self.first(right));
+        if continuing.is_empty() {
+            self.check(left_neighbor, right_neighbor, point);
+        } else {
+            self.check(left_neighbor, continuing.first().copied(), point);
+            self.check(continuing.last().copied(), right_neighbor, point);
+        }
+        let root = self.merge(left, middle);
+        self.merge(root, right)
+    }
+
+    /// Orders two segments that leave the same point by where they are just below it, with
+    /// horizontal segments last.
+    fn order_below(&self, a: usize, b: usize) -> Ordering {
+        let (va, vb) = (self.segments[a].vector(), self.segments[b].vector());
+        match (va.dy == 0.0, vb.dy == 0.0) {
+            (true, true) => a.cmp(&b),
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => (va.dx * vb.dy)
+                .partial_cmp(&(vb.dx * va.dy))
+                .unwrap_or(Ordering::Equal)
+                .then(a.cmp(&b)),
+        }
+    }
+
+    /// Checks if `a`, which is left of `b`, crosses it at `point` without having swapped places
+    /// with it yet.
+    fn crossed(&self, a: usize, b: usize, point: Point<f64>) -> bool {
+        match self.segments[a].intersection(&self.segments[b]) {
+            Intersection::Point(crossing) => {
+                self.near(crossing, point) && self.order_below(a, b) == Ordering::Greater
+            }
+            _ => false,
+        }
+    }
+
+    /// Queues up the point where two neighboring segments cross, if it's below the sweep line.
+    fn check(&mut self, a: Option<usize>, b: Option<usize>, point: Point<f64>) {
+        if let (Some(a), Some(b)) = (a, b) {
+            if let Intersection::Point(crossing) = self.segments[a].intersection(&self.segments[b])
+            {
+                if self.near(crossing, point) {
+                    return;
+                }
+                if sweep_order(crossing, point) == Ordering::Greater {
+                    self.events.push(Reverse(Event(crossing)));
+                } else if (crossing.y - point.y).abs() <= self.tolerance
+                    && self.order_below(a, b) == Ordering::Greater
+                {
+                    // Nearly horizontal segments can cross so close to the sweep line that the
+                    // crossing rounds to just above it, so bring it back down.
+                    self.events
+                        .push(Reverse(Event(Point::new(crossing.x, point.y))));
+                }
+            }
+        }
+    }
+
+    fn node(&mut self, segment: usize) -> usize {
+        // xorshift
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        self.nodes.push(Node {
+            segment,
+            priority: self.seed,
+            left: NONE,
+            right: NONE,
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Splits the treap into the nodes that satisfy `goes_left` and the ones after them.
+    fn split(&mut self, root: usize, goes_left: &impl Fn(&Self, usize) -> bool) -> (usize, usize) {
+        if root == NONE {
+            return (NONE, NONE);
+        }
+        let node = self.nodes[root];
+        if goes_left(self, node.segment) {
+            let (middle, right) = self.split(node.right, goes_left);
+            self.nodes[root].right = middle;
+            (root, right)
+        } else {
+            let (left, middle) = self.split(node.left, goes_left);
+            self.nodes[root].left = middle;
+            (left, root)
+        }
+    }
+
+    fn merge(&mut self, left: usize, right: usize) -> usize {
+        if left == NONE {
+            return right;
+        }
+        if right == NONE {
+            return left;
+        }
+        if self.nodes[left].priority > self.nodes[right].priority {
+            let merged = self.merge(self.nodes[left].right, right);
+            self.nodes[left].right = merged;
+            left
+        } else {
+            let merged = self.merge(left, self.nodes[right].left);
+            self.nodes[right].left = merged;
+            right
+        }
+    }
+
+    /// Removes the leftmost node, returning what's left and the removed node.
+    fn pop_first(&mut self, root: usize) -> (usize, usize) {
+        let node = self.nodes[root];
+        if node.left == NONE {
+            return (node.right, root);
+        }
+        let (rest, first) = self.pop_first(node.left);
+        self.nodes[root].left = rest;
+        (root, first)
+    }
+
+    /// Removes the rightmost node, returning what's left and the removed node.
+    fn pop_last(&mut self, root: usize) -> (usize, usize) {
+        let node = self.nodes[root];
+        if node.right == NONE {
+            return (node.left, root);
+        }
+        let (rest, last) = self.pop_last(node.right);
+        self.nodes[root].right = rest;
+        (root, last)
+    }
+
+    fn collect(&self, root: usize, segments: &mut Vec<usize>) {
+        if root != NONE {
+            let node = self.nodes[root];
+            self.collect(node.left, segments);
+            segments.push(node.segment);
+            self.collect(node.right, segments);
+        }
+    }
+
+    fn first(&self, mut root: usize) -> Option<usize> {
+        if root == NONE {
+            return None;
+        }
+        while self.nodes[root].left != NONE {
+            root = self.nodes[root].left;
+        }
+        Some(self.nodes[root].segment)
+    }
+
+    fn last(&self, mut root: usize) -> Option<usize> {
+        if root == NONE {
+            return None;
+        }
+        while self.nodes[root].right != NONE {
+            root = self.nodes[root].right;
+        }
+        Some(self.nodes[root].segment)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{test::segment, Rect, Size};
+
+    fn crossing(x: f32, y: f32, segments: &[usize]) -> Crossing {
+        Crossing {
+            point: Point::new(x, y),
+            segments: segments.to_vec(),
+        }
+    }
+
+    #[test]
+    fn crossings() {
+        let segments = [
+            segment(0.0, 0.0, 4.0, 4.0),
+            segment(0.0, 4.0, 4.0, 0.0),
+            segment(0.0, 1.0, 4.0, 1.0),
+            segment(5.0, 0.0, 6.0, 1.0),
+        ];
+        assert_eq!(
+            LineSegment::crossings(segments),
+            vec![
+                crossing(1.0, 1.0, &[0, 2]),
+                crossing(3.0, 1.0, &[1, 2]),
+                crossing(2.0, 2.0, &[0, 1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn degenerate() {
+        let segments = [
+            // Three segments through the same point.
+            segment(0.0, 0.0, 2.0, 2.0),
+            segment(2.0, 0.0, 0.0, 2.0),
+            segment(1.0, 0.0, 1.0, 2.0),
+            // One ending on another.
+            segment(0.0, 3.0, 4.0, 3.0),
+            segment(2.0, 3.0, 2.0, 5.0),
+            // Collinear and overlapping.
+            segment(5.0, 0.0, 5.0, 2.0),
+            segment(5.0, 1.0, 5.0, 3.0),
+        ];
+        assert_eq!(
+            LineSegment::crossings(segments),
+            vec![
+                crossing(1.0, 1.0, &[0, 1, 2]),
+                crossing(5.0, 1.0, &[5, 6]),
+                crossing(5.0, 2.0, &[5, 6]),
+                crossing(2.0, 3.0, &[3, 4]),
+            ]
+        );
+    }
+
+    #[test]
+    fn polygon_outline() {
+        // The edges of a closed outline meet at each corner, but nowhere else.
+        let square = Rect::from_top_left(Point::zero(), Size::new(2.0, 2.0));
+        let crossings = LineSegment::crossings(square.line_segments());
+        assert_eq!(crossings.len(), 4);
+        assert!(crossings
+            .iter()
+            .all(|crossing| crossing.segments.len() == 2));
+    }
+}
@@ -0,0 +1,589 @@
+use crate::{incircle, orient2d, HalfPlane, Line, Point, Polygon, Rect, Triangulation, Vector};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{borrow::Borrow, cmp::Ordering};
+
+const NONE: usize = usize::MAX;
+
+/// A Delaunay triangulation of a set of points, meaning that no point lies inside the circumcircle
+/// of any triangle. This maximizes the smallest angle over all triangles, which avoids slivers.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Delaunay<T = f32> {
+    pub points: Vec<Point<T>>,
+    /// Each triangle's points, wound clockwise.
+    pub triangles: Vec<[u32; 3]>,
+    /// For each triangle, the triangles across each of its edges, where the `k`th edge goes from
+    /// point `k` to point `(k + 1) % 3`. Edges along the convex hull have no neighbor.
+    pub neighbors: Vec<[Option<u32>; 3]>,
+}
+
+impl<T: en::Float> Delaunay<T> {
+    /// Triangulates the points, using a sweep-hull algorithm ported from
+    /// [delaunator](https://github.com/mapbox/delaunator). Duplicate points are left out of the
+    /// triangles, and if every point lies on one line, there won't be any triangles at all.
+    ///
+    /// Delaunator is ISC licensed, Copyright (c) 2017, Mapbox; see `THIRD_PARTY_NOTICES.md` for the
+    /// full notice.
+    pub fn new<I>(points: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Point<T>>,
+    {
+        let points: Vec<_> = points.into_iter().map(|point| *point.borrow()).collect();
+        let mut builder = Builder::new(&points);
+        builder.run();
+        let Builder {
+            triangles,
+            halfedges,
+            ..
+        } = builder;
+        let triangles: Vec<_> = triangles
+            .chunks(3)
+            .map(|triangle| [0, 1, 2].map(|k| triangle[k] as u32))
+            .collect();
+        let neighbors = halfedges
+            .chunks(3)
+            .map(|edges| {
+                [0, 1, 2].map(|k| {
+                    Some(edges[k])
+                        .filter(|edge| *edge != NONE)
+                        .map(|edge| (edge / 3) as u32)
+                })
+            })
+            .collect();
+        Self {
+            points,
+            triangles,
+            neighbors,
+        }
+    }
+
+    pub fn triangles(&self) -> impl Iterator<Item = [Point<T>; 3]> + Clone + '_ {
+        self.triangles
+            .iter()
+            .map(move |triangle| triangle.map(|index| self.points[index as usize]))
+    }
+
+    /// Returns the center of the circle passing through each point of the triangle at `index`.
+    /// These are the vertices of the Voronoi diagram.
+    pub fn circumcenter(&self, index: usize) -> Point<T> {
+        let [a, b, c] = self.triangles[index].map(|index| self.points[index as usize]);
+        circumcenter(a, b, c)
+    }
+
+    /// Returns the indices of the points that share an edge with each point. If several points are
+    /// in the same spot, only the first one gets any neighbors.
+    fn adjacency(&self) -> Vec<Vec<usize>> {
+        let mut adjacency = vec![Vec::new(); self.points.len()];
+        let mut order: Vec<_> = (0..self.points.len()).collect();
+        order.sort_by(|a, b| {
+            let (a, b) = (self.points[*a], self.points[*b]);
+            a.x.partial_cmp(&b.x)
+                .unwrap_or(Ordering::Equal)
+                .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+        });
+        if self.triangles.is_empty() {
+            // Without any triangles, the points are all on one line, so each one is next to the
+            // points on either side of it.
+            order.dedup_by_key(|index| self.points[*index]);
+            for pair in order.windows(2) {
+                adjacency[pair[0]].push(pair[1]);
+                adjacency[pair[1]].push(pair[0]);
+            }
+            return adjacency;
+        }
+        for triangle in &self.triangles {
+            for k in 0..3 {
+                let (from, to) = (triangle[k] as usize, triangle[(k + 1) % 3] as usize);
+                adjacency[from].push(to);
+                adjacency[to].push(from);
+            }
+        }
+        // Only one of each set of duplicates made it into the triangulation, but it isn't
+        // necessarily the first one.
+        let mut first = 0;
+        for index in 1..order.len() {
+            if self.points[order[index]] == self.points[order[first]] {
+                let neighbors = std::mem::take(&mut adjacency[order[index]]);
+                adjacency[order[first]].extend(neighbors);
+            } else {
+                first = index;
+            }
+        }
+        for neighbors in &mut adjacency {
+            neighbors.sort_unstable();
+            neighbors.dedup();
+        }
+        adjacency
+    }
+
+    /// Builds the Voronoi diagram, whose cells contain the parts of `bounds` that are closer to
+    /// each point than to any other.
+    pub fn voronoi(&self, bounds: Rect<T>) -> Voronoi<T> {
+        let adjacency = self.adjacency();
+        let cells = self
+            .points
+            .iter()
+            .zip(&adjacency)
+            .enumerate()
+            .map(|(index, (site, neighbors))| {
+                if neighbors.is_empty()
+                    && self.points.len() > 1
+                    && self.points[..index].contains(site)
+                {
+                    return Polygon::default();
+                }
+                neighbors
+                    .iter()
+                    .fold(Polygon::from(bounds), |cell, neighbor| {
+                        clip_to_bisector(&cell, *site, self.points[*neighbor])
+                    })
+            })
+            .collect();
+        Voronoi { cells }
+    }
+}
+
+impl<T: en::Num> From<Delaunay<T>> for Triangulation<T> {
+    fn from(delaunay: Delaunay<T>) -> Self {
+        Self {
+            points: delaunay.points,
+            indices: delaunay.triangles,
+        }
+    }
+}
+
+/// A Voronoi diagram, produced by `Delaunay::voronoi`.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Voronoi<T = f32> {
+    /// The region closest to each point, in the same order as the points. Each cell is convex and
+    /// wound clockwise. If several points are in the same spot, only the first one gets a cell, and
+    /// the others are left empty.
+    pub cells: Vec<Polygon<T>>,
+}
+
+impl<T: en::Float> Voronoi<T> {
+    /// Returns the index of the cell containing `point`, which is the index of the nearest point
+    /// in the original set.
+    pub fn cell_containing(&self, point: Point<T>) -> Option<usize> {
+        self.cells
+            .iter()
+            .position(|cell| cell.len() > 2 && cell.contains(point, crate::FillRule::NonZero))
+    }
+}
+
+fn circumcenter<T: en::Float>(a: Point<T>, b: Point<T>, c: Point<T>) -> Point<T> {
+    let (ab, ac) = (b - a, c - a);
+    let (ab_squared, ac_squared) = (ab.magnitude_squared(), ac.magnitude_squared());
+    let d = ab.cross_product(ac) * T::two();
+    a + Vector::new(
+        ac.dy * ab_squared - ab.dy * ac_squared,
+        ab.dx * ac_squared - ac.dx * ab_squared,
+    ) / d
+}
+
+/// Returns the squared radius of the circle passing through each point, which is infinite if the
+/// points are collinear.
+fn circumradius_squared<T: en::Float>(a: Point<T>, b: Point<T>, c: Point<T>) -> T {
+    if orient2d(a, b, c) == 0.0 {
+        return T::infinity();
+    }
+    (circumcenter(a, b, c) - a).magnitude_squared()
+}
+
+/// Checks if `point` is strictly inside the circumcircle of the clockwise triangle `a`, `b`, `c`.
+fn in_circumcircle<T: en::Float>(a: Point<T>, b: Point<T>, c: Point<T>, point: Point<T>) -> bool {
+    incircle(a, b, c, point) > 0.0
+}
+
+/// Keeps the part of `cell` that's at least as close to `site` as it is to `other`.
+fn clip_to_bisector<T: en::Float>(
+    cell: &Polygon<T>,
+    site: Point<T>,
+    other: Point<T>,
+) -> Polygon<T> {
+    let middle = site + (other - site) / T::two();
+    // The bisector runs so that `site` is on its right.
+    HalfPlane::new(Line::new(middle, (other - site).perpendicular())).clip_polygon(cell)
+}
+
+/// The state of the sweep. Triangles are stored as flat triples of point indices, and each
+/// triangle edge ("halfedge") is identified by its index into `triangles`. `halfedges` links each
+/// halfedge to the one going the opposite way in the adjacent triangle.
+struct Builder<'a, T> {
+    points: &'a [Point<T>],
+    triangles: Vec<usize>,
+    halfedges: Vec<usize>,
+    center: Point<T>,
+    // The convex hull of the points added so far, as a circular doubly-linked list, wound
+    // clockwise.
+    hull_start: usize,
+    hull_next: Vec<usize>,
+    hull_prev: Vec<usize>,
+    /// The halfedge running along the hull from each hull point.
+    hull_tri: Vec<usize>,
+    /// Buckets hull points by their angle around `center`, to find a visible hull edge quickly.
+    hull_hash: Vec<usize>,
+    edge_stack: Vec<usize>,
+}
+
+impl<'a, T: en::Float> Builder<'a, T> {
+    fn new(points: &'a [Point<T>]) -> Self {
+        let len = points.len();
+        let max_triangles = (2 * len).saturating_sub(5).max(1);
+        Self {
+            points,
+            triangles: Vec::with_capacity(max_triangles * 3),
+            halfedges: Vec::with_capacity(max_triangles * 3),
+            center: Point::zero(),
+            hull_start: 0,
+            hull_next: vec![NONE; len],
+            hull_prev: vec![NONE; len],
+            hull_tri: vec![NONE; len],
+            hull_hash: vec![NONE; (len as f64).sqrt().ceil() as usize],
+            edge_stack: Vec::new(),
+        }
+    }
+
+    /// Checks if `point` can see the hull edge from `from` to `to`, meaning that it's on the
+    /// outside of it.
+    fn is_visible(&self, point: usize, from: usize, to: usize) -> bool {
+        let (point, from, to) = (self.points[point], self.points[from], self.points[to]);
+        orient2d(from, to, point) < 0.0
+    }
+
+    fn hash_key(&self, point: Point<T>) -> usize {
+        // This pseudo-angle increases monotonically with the true angle, but is cheaper.
+        let delta = point - self.center;
+        let sum = delta.dx.abs() + delta.dy.abs();
+        let p = if sum == T::zero() {
+            T::zero()
+        } else {
+            delta.dx / sum
+        };
+        let angle = if delta.dy > T::zero() {
+            T::three() - p
+        } else {
+            T::one() + p
+        } / (T::two() + T::two());
+        let len = self.hull_hash.len();
+        (en::cast::<usize, _>((angle * en::cast(len)).floor()).min(len - 1)) % len
+    }
+
+    fn run(&mut self) {
+        let points = self.points;
+        if points.len() < 3 {
+            return;
+        }
+        let bounds = Rect::from_iter(points);
+        let center = bounds.center();
+        let distance = |a: Point<T>, b: Point<T>| (a - b).magnitude_squared();
+        let closest = |to: Point<T>, skip: &[usize]| {
+            (0..points.len())
+                .filter(|index| !skip.contains(index))
+                .min_by(|a, b| {
+                    distance(points[*a], to)
+                        .partial_cmp(&distance(points[*b], to))
+                        .unwrap_or(Ordering::Equal)
+                })
+                .expect("there are at least three points")
+        };
+
+        // The seed triangle is the point nearest the center, the point nearest to that, and the
+        // point that makes the smallest circumcircle with those two.
+        let i0 = closest(center, &[]);
+        let mut i1 = match (0..points.len())
+            .filter(|index| points[*index] != points[i0])
+            .min_by(|a, b| {
+                distance(points[*a], points[i0])
+                    .partial_cmp(&distance(points[*b], points[i0]))
+                    .unwrap_or(Ordering::Equal)
+            }) {
+            Some(i1) => i1,
+            None => return,
+        };
+        let mut i2 = NONE;
+        let mut min_radius = T::infinity();
+        for (index, point) in points.iter().enumerate() {
+            let radius = circumradius_squared(points[i0], points[i1], *point);
+            if radius < min_radius {
+                i2 = index;
+                min_radius = radius;
+            }
+        }
+        if i2 == NONE {
+            return;
+        }
+        if orient2d(points[i0], points[i1], points[i2]) < 0.0 {
+            std::mem::swap(&mut i1, &mut i2);
+        }
+        self.center = circumcenter(points[i0], points[i1], points[i2]);
+
+        // Points are added in order of their distance from the seed triangle, so that each new
+        // point is always outside of the hull so far.
+        let mut order: Vec<_> = (0..points.len()).collect();
+        let center = self.center;
+        order.sort_by(|a, b| {
+            distance(points[*a], center)
+                .partial_cmp(&distance(points[*b], center))
+                .unwrap_or(Ordering::Equal)
+        });
+
+        self.hull_start = i0;
+        self.hull_next[i0] = i1;
+        self.hull_prev[i2] = i1;
+        self.hull_next[i1] = i2;
+        self.hull_prev[i0] = i2;
+        self.hull_next[i2] = i0;
+        self.hull_prev[i1] = i0;
+        self.hull_tri[i0] = 0;
+        self.hull_tri[i1] = 1;
+        self.hull_tri[i2] = 2;
+        for index in [i0, i1, i2] {
+            let key = self.hash_key(points[index]);
+            self.hull_hash[key] = index;
+        }
+        self.add_triangle(i0, i1, i2, NONE, NONE, NONE);
+
+        for index in order {
+            if index == i0 || index == i1 || index == i2 {
+                continue;
+            }
+            self.insert(index);
+        }
+    }
+
+    fn insert(&mut self, index: usize) {
+        let point = self.points[index];
+        // Find a hull point with a similar angle, then walk along the hull to find an edge that's
+        // visible from the new point.
+        let key = self.hash_key(point);
+        let len = self.hull_hash.len();
+        let mut start = NONE;
+        for offset in 0..len {
+            start = self.hull_hash[(key + offset) % len];
+            if start != NONE && start != self.hull_next[start] {
+                break;
+            }
+        }
+        start = self.hull_prev[start];
+        let mut e = start;
+        loop {
+            let q = self.hull_next[e];
+            if self.is_visible(index, e, q) {
+                break;
+            }
+            e = q;
+            if e == start {
+                // The point is on the hull already, so it must be a duplicate.
+                return;
+            }
+        }
+
+        let t = self.add_triangle(e, index, self.hull_next[e], NONE, NONE, self.hull_tri[e]);
+        self.hull_tri[index] = self.legalize(t + 2);
+        self.hull_tri[e] = t;
+
+        // Add triangles for the rest of the visible edges going forward...
+        let mut n = self.hull_next[e];
+        loop {
+            let q = self.hull_next[n];
+            if !self.is_visible(index, n, q) {
+                break;
+            }
+            let t = self.add_triangle(n, index, q, self.hull_tri[index], NONE, self.hull_tri[n]);
+            self.hull_tri[index] = self.legalize(t + 2);
+            self.hull_next[n] = n;
+            n = q;
+        }
+        // ...and going backward.
+        if e == start {
+            loop {
+                let q = self.hull_prev[e];
+                if !self.is_visible(index, q, e) {
+                    break;
+                }
+                let t = self.add_triangle(q, index, e, NONE, self.hull_tri[e], self.hull_tri[q]);
+                self.legalize(t + 2);
+                self.hull_tri[q] = t;
+                self.hull_next[e] = e;
+                e = q;
+            }
+        }
+
+        self.hull_start = e;
+        self.hull_prev[index] = e;
+        self.hull_next[e] = index;
+        self.hull_prev[n] = index;
+        self.hull_next[index] = n;
+        let key = self.hash_key(point);
+        self.hull_hash[key] = index;
+        let key = self.hash_key(self.points[e]);
+        self.hull_hash[key] = e;
+    }
+
+    fn link(&mut self, a: usize, b: usize) {
+        self.halfedges[a] = b;
+        if b != NONE {
+            self.halfedges[b] = a;
+        }
+    }
+
+    fn add_triangle(
+        &mut self,
+        i0: usize,
+        i1: usize,
+        i2: usize,
+        a: usize,
+        b: usize,
+        c: usize,
+    ) -> usize {
+        let t = self.triangles.len();
+        self.triangles.extend([i0, i1, i2]);
+        self.halfedges.extend([NONE; 3]);
+        self.link(t, a);
+        self.link(t + 1, b);
+        self.link(t + 2, c);
+        t
+    }
+
+    /// Flips edges until every triangle around halfedge `a` satisfies the Delaunay condition,
+    /// returning the halfedge that ends up opposite the newly added point.
+    fn legalize(&mut self, mut a: usize) -> usize {
+        let mut ar;
+        loop {
+            let b = self.halfedges[a];
+            let a0 = a - a % 3;
+            ar = a0 + (a + 2) % 3;
+            if b == NONE {
+                match self.edge_stack.pop() {
+                    Some(next) => {
+                        a = next;
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+            let b0 = b - b % 3;
+            let al = a0 + (a + 1) % 3;
+            let bl = b0 + (b + 2) % 3;
+            let [p0, pr, pl, p1] = [ar, a, al, bl].map(|edge| self.points[self.triangles[edge]]);
+            if in_circumcircle(p0, pr, pl, p1) {
+                self.triangles[a] = self.triangles[bl];
+                self.triangles[b] = self.triangles[ar];
+                let hbl = self.halfedges[bl];
+                if hbl == NONE {
+                    // The flipped edge was on the hull, so the hull needs to point at its new
+                    // halfedge.
+                    let mut e = self.hull_start;
+                    loop {
+                        if self.hull_tri[e] == bl {
+                            self.hull_tri[e] = a;
+                            break;
+                        }
+                        e = self.hull_prev[e];
+                        if e == self.hull_start {
+                            break;
+                        }
+                    }
+                }
+                self.link(a, hbl);
+                self.link(b, self.halfedges[ar]);
+                self.link(ar, bl);
+                self.edge_stack.push(b0 + (b + 1) % 3);
+            } else {
+                match self.edge_stack.pop() {
+                    Some(next) => a = next,
+                    None => break,
+                }
+            }
+        }
+        ar
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, Size};
+
+    fn grid(size: usize) -> Vec<Point> {
+        (0..size * size)
+            .map(|index| Point::new((index % size) as f32, (index / size) as f32))
+            .collect()
+    }
+
+    #[test]
+    fn delaunay() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 3.0),
+            Point::new(0.0, 3.0),
+            Point::new(1.0, 1.0),
+        ];
+        let delaunay = Delaunay::new(points);
+        assert_eq!(delaunay.triangles.len(), 4);
+        for [a, b, c] in delaunay.triangles() {
+            assert!((b - a).cross_product(c - a) > 0.0);
+        }
+        let triangulation = Triangulation::from(delaunay.clone());
+        assert_approx_eq!(triangulation.area(), 12.0);
+        // Each interior edge is shared by two triangles, and each hull edge has no neighbor.
+        let hull_edges = delaunay
+            .neighbors
+            .iter()
+            .flatten()
+            .filter(|neighbor| neighbor.is_none())
+            .count();
+        assert_eq!(hull_edges, 4);
+        for (index, neighbors) in delaunay.neighbors.iter().enumerate() {
+            for neighbor in neighbors.iter().flatten() {
+                assert!(delaunay.neighbors[*neighbor as usize].contains(&Some(index as u32)));
+            }
+        }
+    }
+
+    #[test]
+    fn grid_and_duplicates() {
+        let mut points = grid(5);
+        points.push(Point::new(2.0, 2.0));
+        let delaunay = Delaunay::new(&points);
+        // Triangulating `n` points with `h` of them on the hull makes `2n - h - 2` triangles.
+        assert_eq!(delaunay.triangles.len(), 2 * 25 - 16 - 2);
+        assert_approx_eq!(Triangulation::from(delaunay).area(), 16.0);
+        assert!(Delaunay::new([
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 2.0)
+        ])
+        .triangles
+        .is_empty());
+    }
+
+    #[test]
+    fn voronoi() {
+        let points = grid(3);
+        let bounds = Rect::from_top_left(Point::new(-0.5, -0.5), Size::square(3.0));
+        let voronoi = Delaunay::new(&points).voronoi(bounds);
+        assert_eq!(voronoi.cells.len(), 9);
+        for cell in &voronoi.cells {
+            assert_approx_eq!(cell.area(), 1.0);
+            assert!(cell.signed_area() > 0.0);
+        }
+        assert_eq!(voronoi.cell_containing(Point::new(1.2, 1.9)), Some(7));
+
+        // Collinear points get strips.
+        let line = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(1.0, 0.0),
+        ];
+        let bounds = Rect::from_top_left(Point::new(-0.5, -1.0), Size::new(3.0, 2.0));
+        let voronoi = Delaunay::new(line).voronoi(bounds);
+        assert_approx_eq!(voronoi.cells[2].area(), 2.0);
+        assert_eq!(voronoi.cell_containing(Point::new(1.8, 0.5)), Some(1));
+    }
+}
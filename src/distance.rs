@@ -0,0 +1,261 @@
+use crate::{
+    Capsule, Circle, CubicBezier, Ellipse, FillRule, Line, LineSegment, MultiPolygon, Point,
+    Polygon, PolygonWithHoles, Polyline, Quad, QuadBezier, Ray, Rect,
+};
+
+/// A shape that can find its closest point to any other point. Shapes with an inside are solid, so
+/// points inside of them are their own closest point. Shapes without any points at all (like an
+/// empty `Polygon`) are infinitely far away.
+pub trait Distance<T: en::Float> {
+    /// Returns the point on or inside the shape that's nearest to `point`.
+    fn closest_point(&self, point: Point<T>) -> Point<T>;
+
+    /// Returns how far `point` is from the shape, which is zero if it's inside.
+    fn distance_to(&self, point: Point<T>) -> T {
+        (self.closest_point(point) - point).magnitude()
+    }
+}
+
+impl<T: en::Float, S: Distance<T> + ?Sized> Distance<T> for &S {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        (**self).closest_point(point)
+    }
+}
+
+impl<T: en::Float, S: Distance<T> + ?Sized> Distance<T> for Box<S> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        (**self).closest_point(point)
+    }
+}
+
+/// Finds the closest point on any of `segments`.
+fn closest_on_segments<T: en::Float>(
+    segments: impl IntoIterator<Item = LineSegment<T>>,
+    point: Point<T>,
+) -> Point<T> {
+    let mut closest = Point::new(T::infinity(), T::infinity());
+    let mut closest_distance = T::infinity();
+    for segment in segments {
+        let candidate = segment.closest_point(point);
+        let distance = (candidate - point).magnitude_squared();
+        if distance < closest_distance {
+            closest = candidate;
+            closest_distance = distance;
+        }
+    }
+    closest
+}
+
+impl<T: en::Float> Distance<T> for LineSegment<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        let vector = self.vector();
+        let length_squared = vector.magnitude_squared();
+        if length_squared == T::zero() {
+            return self.from;
+        }
+        let t = (point - self.from).dot_product(vector) / length_squared;
+        if t <= T::zero() {
+            self.from
+        } else if t >= T::one() {
+            self.to
+        } else {
+            self.from + vector * t
+        }
+    }
+}
+
+impl<T: en::Float> Distance<T> for Ray<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        let direction = self.unit_vector();
+        let t = (point - self.point).dot_product(direction);
+        if t <= T::zero() {
+            self.point
+        } else {
+            self.point + direction * t
+        }
+    }
+}
+
+impl<T: en::Float> Distance<T> for Line<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        self.project(point)
+    }
+}
+
+impl<T: en::Float> Distance<T> for Polyline<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        match self.points() {
+            [only] => *only,
+            _ => closest_on_segments(self.line_segments(), point),
+        }
+    }
+}
+
+impl<T: en::Float> Distance<T> for QuadBezier<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        self.point_at(self.closest_t(point))
+    }
+}
+
+impl<T: en::Float> Distance<T> for CubicBezier<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        self.point_at(self.closest_t(point))
+    }
+}
+
+impl<T: en::Float> Distance<T> for Rect<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        let clamp = |value: T, min: T, max: T| en::Num::min(en::Num::max(value, min), max);
+        Point::new(
+            clamp(point.x, self.left(), self.right()),
+            clamp(point.y, self.top(), self.bottom()),
+        )
+    }
+}
+
+impl<T: en::Float> Distance<T> for Quad<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        Polygon::from(*self).closest_point(point)
+    }
+}
+
+impl<T: en::Float> Distance<T> for Polygon<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        if self.contains(point, FillRule::NonZero) {
+            return point;
+        }
+        closest_on_segments(self.line_segments(), point)
+    }
+}
+
+impl<T: en::Float> Distance<T> for PolygonWithHoles<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        if self.contains(point) {
+            return point;
+        }
+        closest_on_segments(self.rings().flat_map(Polygon::line_segments), point)
+    }
+}
+
+impl<T: en::Float> Distance<T> for MultiPolygon<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        if self.contains(point) {
+            return point;
+        }
+        closest_on_segments(self.rings().flat_map(Polygon::line_segments), point)
+    }
+}
+
+impl<T: en::Float> Distance<T> for Circle<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        let offset = point - self.center();
+        let distance = offset.magnitude();
+        if distance <= self.radius() {
+            point
+        } else {
+            self.center() + offset * (self.radius() / distance)
+        }
+    }
+}
+
+impl<T: en::Float> Distance<T> for Ellipse<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        if self.contains(point) {
+            point
+        } else {
+            self.closest_outline_point(point)
+        }
+    }
+}
+
+impl<T: en::Float> Distance<T> for Capsule<T> {
+    fn closest_point(&self, point: Point<T>) -> Point<T> {
+        let nearest = self.segment.closest_point(point);
+        let offset = point - nearest;
+        let distance = offset.magnitude();
+        if distance <= self.radius {
+            point
+        } else {
+            nearest + offset * (self.radius / distance)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, Angle, Size, Vector};
+
+    fn assert_closest(shape: &impl Distance<f32>, point: Point, expected: Point) {
+        let closest = shape.closest_point(point);
+        assert_approx_eq!(closest.x, expected.x);
+        assert_approx_eq!(closest.y, expected.y);
+        assert_approx_eq!(shape.distance_to(point), (expected - point).magnitude());
+    }
+
+    #[test]
+    fn open_shapes() {
+        let segment = LineSegment::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+        assert_closest(&segment, Point::new(2.0, 3.0), Point::new(2.0, 0.0));
+        assert_closest(&segment, Point::new(7.0, -4.0), Point::new(4.0, 0.0));
+        let ray = Ray::new(Point::new(0.0, 0.0), Angle::ZERO());
+        assert_closest(&ray, Point::new(9.0, 3.0), Point::new(9.0, 0.0));
+        assert_closest(&ray, Point::new(-3.0, 4.0), Point::new(0.0, 0.0));
+        let line = Line::new(Point::new(0.0, 0.0), Vector::new(1.0, 0.0));
+        assert_closest(&line, Point::new(-3.0, 4.0), Point::new(-3.0, 0.0));
+        let polyline = Polyline::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 4.0),
+        ]);
+        assert_closest(&polyline, Point::new(3.0, 3.0), Point::new(4.0, 3.0));
+        assert_closest(&polyline, Point::new(1.0, 3.0), Point::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn solid_shapes() {
+        let rect = Rect::from_top_left(Point::zero(), Size::new(4.0, 2.0));
+        assert_closest(&rect, Point::new(6.0, -1.0), Point::new(4.0, 0.0));
+        assert_closest(&rect, Point::new(1.0, 1.0), Point::new(1.0, 1.0));
+        let polygon = Polygon::from(rect);
+        assert_closest(&polygon, Point::new(6.0, -1.0), Point::new(4.0, 0.0));
+        assert_closest(&polygon, Point::new(2.0, 5.0), Point::new(2.0, 2.0));
+        assert_closest(&polygon, Point::new(1.0, 1.0), Point::new(1.0, 1.0));
+        let circle = Circle::new(Point::new(1.0, 1.0), 2.0);
+        assert_closest(&circle, Point::new(1.0, 5.0), Point::new(1.0, 3.0));
+        assert_closest(&circle, Point::new(2.0, 1.0), Point::new(2.0, 1.0));
+        let capsule = Capsule::from_points(Point::new(0.0, 0.0), Point::new(4.0, 0.0), 1.0);
+        assert_closest(&capsule, Point::new(2.0, 3.0), Point::new(2.0, 1.0));
+        assert_closest(&capsule, Point::new(-3.0, 0.0), Point::new(-1.0, 0.0));
+        assert_eq!(
+            Polygon::<f32>::default().distance_to(Point::zero()),
+            f32::INFINITY
+        );
+    }
+
+    #[test]
+    fn holes() {
+        let outer = Polygon::from(Rect::from_top_left(Point::zero(), Size::new(6.0, 6.0)));
+        let hole = Polygon::from(Rect::from_top_left(
+            Point::new(2.0, 2.0),
+            Size::new(2.0, 2.0),
+        ));
+        let shape = PolygonWithHoles::new(outer, vec![hole]);
+        assert_closest(&shape, Point::new(3.0, 2.5), Point::new(3.0, 2.0));
+        assert_closest(&shape, Point::new(1.0, 1.0), Point::new(1.0, 1.0));
+    }
+
+    #[test]
+    fn ellipses() {
+        let ellipse = Ellipse::new(Point::new(1.0, 1.0), Size::new(4.0, 2.0));
+        assert_closest(&ellipse, Point::new(1.0, 6.0), Point::new(1.0, 3.0));
+        assert_closest(&ellipse, Point::new(9.0, 1.0), Point::new(5.0, 1.0));
+        assert_closest(&ellipse, Point::new(2.0, 2.0), Point::new(2.0, 2.0));
+        // Off the axes, the closest point is where the outline's normal points at `point`.
+        let point = Point::new(6.0, 5.0);
+        let closest = ellipse.closest_point(point);
+        let normal = ellipse.outline_normal(closest);
+        assert_approx_eq!(normal.cross_product((point - closest).normalize()), 0.0);
+        assert!(ellipse.distance_to(point) > 0.0);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Angle, Circle, Point, Rect, Size, Vector};
+use crate::{Angle, Circle, EllipseArc, Point, Polygon, Rect, Size, Vector};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::ops::{Add, AddAssign, Sub, SubAssign};
@@ -112,6 +112,22 @@ impl<T: en::Num> Ellipse<T> {
         self.arc_points(steps, start_angle, start_angle + Angle::TAU())
     }
 
+    /// Approximates the ellipse with a polygon that strays from it by no more than `tolerance`,
+    /// which should be positive. Unlike `ellipse_points`, the number of points depends on how big
+    /// the ellipse is, so that small ellipses don't get more points than they need and big ones
+    /// don't look jagged.
+    pub fn flatten(&self, tolerance: T) -> Polygon<T>
+    where
+        T: en::Float,
+    {
+        let mut points = EllipseArc::new(*self, Angle::ZERO(), Angle::TAU())
+            .flatten(tolerance)
+            .into_points();
+        // The last point is back at the start.
+        points.pop();
+        Polygon::new(points)
+    }
+
     pub fn map<U: en::Num>(
         self,
         f: impl FnOnce(Point<T>, Size<T>) -> (Point<U>, Size<U>),
@@ -139,6 +155,101 @@ impl<T: en::Num> Ellipse<T> {
     impl_casts!(Ellipse);
 }
 
+impl<T: en::Float> Ellipse<T> {
+    /// Returns the point on the ellipse's outline that's closest to `point`, which can be inside or
+    /// outside of the ellipse. This uses the bisection method from David Eberly's "Distance from a
+    /// Point to an Ellipse, an Ellipsoid, or a Hyperellipsoid".
+    pub(crate) fn closest_outline_point(&self, point: Point<T>) -> Point<T> {
+        let offset = point - self.center;
+        let (rx, ry) = (self.radius.width(), self.radius.height());
+        // The problem is symmetric, so solve it in the positive quadrant with the longer axis along
+        // x, and then map the answer back.
+        let swap = rx < ry;
+        let (major, minor) = if swap { (ry, rx) } else { (rx, ry) };
+        let (x, y) = if swap {
+            (offset.dy.abs(), offset.dx.abs())
+        } else {
+            (offset.dx.abs(), offset.dy.abs())
+        };
+        let (x, y) = closest_in_quadrant(major, minor, x, y);
+        let (x, y) = if swap { (y, x) } else { (x, y) };
+        let x = if offset.dx < T::zero() { -x } else { x };
+        let y = if offset.dy < T::zero() { -y } else { y };
+        self.center + Vector::new(x, y)
+    }
+
+    /// Returns the outward-facing unit normal at a point on the ellipse's outline.
+    pub(crate) fn outline_normal(&self, point: Point<T>) -> Vector<T> {
+        let offset = point - self.center;
+        let radius_squared = self.radius_squared();
+        Vector::new(
+            offset.dx / radius_squared.width(),
+            offset.dy / radius_squared.height(),
+        )
+        .normalize()
+    }
+}
+
+/// Finds the closest point to `(x, y)` on the quarter of an ellipse with radii `major >= minor` that
+/// lies in the positive quadrant, given that `x` and `y` are non-negative.
+fn closest_in_quadrant<T: en::Float>(major: T, minor: T, x: T, y: T) -> (T, T) {
+    let zero = T::zero();
+    if minor == zero {
+        return (en::Num::min(x, major), zero);
+    }
+    if y > zero {
+        if x > zero {
+            let (zx, zy) = (x / major, y / minor);
+            let g = zx * zx + zy * zy - T::one();
+            if g == zero {
+                return (x, y);
+            }
+            let ratio = (major / minor) * (major / minor);
+            let s = bisect_root(ratio, zx, zy, g);
+            (ratio * x / (s + ratio), y / (s + T::one()))
+        } else {
+            (zero, minor)
+        }
+    } else {
+        let numerator = major * x;
+        let denominator = major * major - minor * minor;
+        if numerator < denominator {
+            let xd = numerator / denominator;
+            (major * xd, minor * (T::one() - xd * xd).sqrt())
+        } else {
+            (major, zero)
+        }
+    }
+}
+
+fn bisect_root<T: en::Float>(ratio: T, zx: T, zy: T, g: T) -> T {
+    let n = ratio * zx;
+    let mut low = zy - T::one();
+    let mut high = if g < T::zero() {
+        T::zero()
+    } else {
+        n.hypot(zy) - T::one()
+    };
+    let mut s = low;
+    // Each step halves the interval, so this is plenty to exhaust the precision of an `f64`.
+    for _ in 0..1100 {
+        s = (low + high) / T::two();
+        if s == low || s == high {
+            break;
+        }
+        let (rx, ry) = (n / (s + ratio), zy / (s + T::one()));
+        let g = rx * rx + ry * ry - T::one();
+        if g > T::zero() {
+            low = s;
+        } else if g < T::zero() {
+            high = s;
+        } else {
+            break;
+        }
+    }
+    s
+}
+
 impl<T: en::Num> Add<Vector<T>> for Ellipse<T> {
     type Output = Self;
     fn add(self, rhs: Vector<T>) -> Self::Output {
@@ -174,7 +285,7 @@ impl<T: en::Num> From<Circle<T>> for Ellipse<T> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::assert_approx_eq;
+    use crate::{assert_approx_eq, Distance, Polyline};
 
     #[test]
     fn bounding_rect() {
@@ -225,4 +336,34 @@ mod test {
         check!(Point::new(-1.0, 4.5), Size::square(3.0));
         check!(Point::new(3.0, -2.5), Size::new(2.5, 6.0));
     }
+
+    #[test]
+    fn flatten() {
+        let radius = 100.0;
+        let polygon = Circle::new(Point::new(3.0, 4.0), radius).flatten(0.1);
+        let points = polygon.points();
+        // The middle of each edge is the furthest it gets from the circle.
+        for edge in polygon.line_segments() {
+            let middle = edge.from + edge.vector() / 2.0;
+            // This allows for rounding, since the edges go as far as they can.
+            assert!(radius - (middle - Point::new(3.0, 4.0)).magnitude() <= 0.1001);
+        }
+        // Any fewer points would stray too far.
+        let fewer = std::f32::consts::PI / (points.len() - 1) as f32;
+        assert!(radius * (1.0 - fewer.cos()) > 0.1);
+        // Smaller circles need fewer points for the same tolerance.
+        let small = Circle::new(Point::zero(), 1.0).flatten(0.1);
+        assert!(small.len() < points.len());
+        // A thin ellipse is nearly flat along its long sides, so it only needs many points around
+        // its ends.
+        let ellipse = Ellipse::new(Point::zero(), Size::new(radius, 1.0));
+        let thin = ellipse.flatten(0.1);
+        assert!(thin.len() * 4 < points.len());
+        let mut outline = thin.into_points();
+        outline.push(outline[0]);
+        let outline = Polyline::new(outline);
+        for point in ellipse.ellipse_points(1000, Angle::ZERO()) {
+            assert!(outline.distance_to(point) <= 0.1001);
+        }
+    }
 }
@@ -0,0 +1,315 @@
+use crate::{
+    bezier::Curve, Angle, CubicBezier, Ellipse, Point, Polyline, Rect, Size, Transform, Vector,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The part of an ellipse's outline running from `start_angle` to `end_angle`. Like
+/// `Ellipse::arc_points`, angles increase counter-clockwise on screen, so the arc runs clockwise if
+/// `end_angle` is less than `start_angle`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct EllipseArc<T = f32> {
+    pub ellipse: Ellipse<T>,
+    pub start_angle: Angle<T>,
+    pub end_angle: Angle<T>,
+}
+
+impl<T: en::Float> Curve<T> for EllipseArc<T> {
+    fn position(&self, t: T) -> Point<T> {
+        self.point_at(t)
+    }
+
+    fn velocity(&self, t: T) -> Vector<T> {
+        self.derivative(t)
+    }
+
+    fn acceleration(&self, t: T) -> Vector<T> {
+        let sweep = self.sweep().radians();
+        let (sin, cos) = self.angle_at(t).sin_cos();
+        Vector::new(-cos, sin).scaled(self.ellipse.radius()) * (sweep * sweep)
+    }
+
+    fn hull_length(&self) -> T {
+        self.sweep().radians().abs() * self.ellipse.radius().max_dim()
+    }
+
+    /// An arc that heads along the line between its ends at both ends stays between the lines
+    /// perpendicular to it there, as long as it turns less than halfway around. Since it's a
+    /// stretched circular arc, it's then furthest from the line in its middle.
+    fn is_flat(&self, start: T, end: T, tolerance: T) -> bool {
+        let piece = Self::new(self.ellipse, self.angle_at(start), self.angle_at(end));
+        if piece.sweep().radians().abs() > T::PI() {
+            return false;
+        }
+        let (from, middle) = (piece.from(), piece.point_at(T::one().halved()));
+        let chord = piece.to() - from;
+        let length = chord.magnitude();
+        if length == T::zero() {
+            return (middle - from).magnitude() <= tolerance;
+        }
+        piece.derivative(T::zero()).dot_product(chord) >= T::zero()
+            && piece.derivative(T::one()).dot_product(chord) >= T::zero()
+            && chord.cross_product(middle - from).abs() <= tolerance * length
+    }
+}
+
+impl<T: en::Float> EllipseArc<T> {
+    pub fn new(ellipse: Ellipse<T>, start_angle: Angle<T>, end_angle: Angle<T>) -> Self {
+        Self {
+            ellipse,
+            start_angle,
+            end_angle,
+        }
+    }
+
+    /// Returns how far the arc turns, which is negative if it runs clockwise.
+    pub fn sweep(&self) -> Angle<T> {
+        self.end_angle - self.start_angle
+    }
+
+    pub fn from(&self) -> Point<T> {
+        self.point_at(T::zero())
+    }
+
+    pub fn to(&self) -> Point<T> {
+        self.point_at(T::one())
+    }
+
+    /// Returns the same arc with its direction reversed.
+    pub fn reverse(self) -> Self {
+        Self::new(self.ellipse, self.end_angle, self.start_angle)
+    }
+
+    fn angle_at(&self, t: T) -> Angle<T> {
+        self.start_angle + self.sweep() * t
+    }
+
+    fn point_at_angle(&self, angle: Angle<T>) -> Point<T> {
+        self.ellipse.center() + angle.unit_vector().scaled(self.ellipse.radius())
+    }
+
+    /// Returns the point `t` of the way along the arc, where `t` is in `0..=1`.
+    pub fn point_at(&self, t: T) -> Point<T> {
+        self.point_at_angle(self.angle_at(t))
+    }
+
+    /// Returns the velocity of a point moving along the arc as `t` goes from 0 to 1.
+    pub fn derivative(&self, t: T) -> Vector<T> {
+        let (sin, cos) = self.angle_at(t).sin_cos();
+        Vector::new(-sin, -cos).scaled(self.ellipse.radius()) * self.sweep().radians()
+    }
+
+    /// Returns the unit vector pointing along the arc.
+    pub fn tangent(&self, t: T) -> Vector<T> {
+        self.derivative(t).normalize()
+    }
+
+    /// Returns the unit vector perpendicular to the arc, pointing to the right.
+    pub fn normal(&self, t: T) -> Vector<T> {
+        self.tangent(t).perpendicular()
+    }
+
+    /// Returns the smallest rect containing the arc.
+    pub fn bounding_rect(&self) -> Rect<T> {
+        let (start, end) = (self.start_angle.radians(), self.end_angle.radians());
+        let (low, high) = if start < end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+        if high - low >= T::TAU() {
+            return self.ellipse.bounding_rect();
+        }
+        // The arc reaches the edges of the ellipse's bounds at each quarter turn.
+        let quarter = T::FRAC_PI_2();
+        let mut angle = (low / quarter).ceil() * quarter;
+        let mut points = vec![self.from(), self.to()];
+        while angle < high {
+            points.push(self.point_at_angle(Angle::from_radians(angle)));
+            angle = angle + quarter;
+        }
+        Rect::from_iter(points)
+    }
+
+    pub fn length(&self) -> T {
+        self.length_between(T::zero(), T::one())
+    }
+
+    /// Returns the `t` where the length of the arc up to that point is `distance`. This is only
+    /// different from `distance / length` for arcs of ellipses that aren't circles.
+    pub fn t_at_length(&self, distance: T) -> T {
+        Curve::t_at_length(self, distance)
+    }
+
+    /// Returns the point that's `distance` along the arc, clamped to its endpoints.
+    pub fn point_at_length(&self, distance: T) -> Point<T> {
+        self.point_at(self.t_at_length(distance))
+    }
+
+    /// Approximates the arc with a polyline that strays from it by no more than `tolerance`, which
+    /// should be positive. The polyline includes both endpoints, and uses fewer points where the arc
+    /// is flatter. For an arc of a circle, no fewer points along the arc could do so.
+    pub fn flatten(&self, tolerance: T) -> Polyline<T> {
+        Curve::flatten(self, tolerance)
+    }
+
+    /// Approximates the arc using a cubic Bézier curve for every quarter turn or less. For a circle,
+    /// each curve is off by less than 0.03% of the radius.
+    pub fn to_cubic_beziers(&self) -> Vec<CubicBezier<T>> {
+        let sweep = self.sweep().radians();
+        let count = en::cast::<usize, _>((sweep.abs() / T::FRAC_PI_2()).ceil()).max(1);
+        let step = sweep / en::cast(count);
+        // How far along the tangents to place the control points.
+        let k = (step / en::cast(4)).tan() * en::cast(4) / T::three();
+        let radius = self.ellipse.radius();
+        let tangent = |angle: T| {
+            let (sin, cos) = angle.sin_cos();
+            Vector::new(-sin, -cos).scaled(radius) * k
+        };
+        (0..count)
+            .map(|index| {
+                let from = self.start_angle.radians() + step * en::cast(index);
+                let to = from + step;
+                let (a, b) = (
+                    self.point_at_angle(Angle::from_radians(from)),
+                    self.point_at_angle(Angle::from_radians(to)),
+                );
+                CubicBezier::new(a, a + tangent(from), b - tangent(to), b)
+            })
+            .collect()
+    }
+
+    /// Returns the transformed arc, as long as `transform` doesn't rotate or skew it. Those would
+    /// need an ellipse that isn't aligned with the axes, so they can be handled by transforming
+    /// `to_cubic_beziers` instead.
+    pub fn transform(self, transform: Transform<T>) -> Option<Self> {
+        if transform.m12 != T::zero() || transform.m21 != T::zero() {
+            return None;
+        }
+        let radius = self.ellipse.radius();
+        let ellipse = Ellipse::new(
+            self.ellipse.center().transform(transform),
+            Size::new(
+                radius.width() * transform.m11.abs(),
+                radius.height() * transform.m22.abs(),
+            ),
+        );
+        // Flipping either axis mirrors the angles, which also flips the arc's direction.
+        let flip = |angle: Angle<T>| {
+            let angle = if transform.m11 < T::zero() {
+                Angle::PI() - angle
+            } else {
+                angle
+            };
+            if transform.m22 < T::zero() {
+                -angle
+            } else {
+                angle
+            }
+        };
+        Some(Self::new(
+            ellipse,
+            flip(self.start_angle),
+            flip(self.end_angle),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, assert_points_eq, Distance};
+
+    #[test]
+    fn evaluate() {
+        let arc = EllipseArc::new(
+            Ellipse::new(Point::new(1.0, 1.0), Size::new(2.0, 1.0)),
+            Angle::ZERO(),
+            Angle::PI(),
+        );
+        assert_points_eq!(arc.from(), Point::new(3.0, 1.0));
+        // Counter-clockwise on screen goes up first.
+        assert_points_eq!(arc.point_at(0.5), Point::new(1.0, 0.0));
+        assert_points_eq!(arc.to(), Point::new(-1.0, 1.0));
+        assert_points_eq!(arc.tangent(0.0).to_point(), Point::new(0.0, -1.0));
+        assert_points_eq!(arc.tangent(0.5).to_point(), Point::new(-1.0, 0.0));
+        let rect = arc.bounding_rect();
+        assert_points_eq!(rect.top_left(), Point::new(-1.0, 0.0));
+        assert_points_eq!(rect.bottom_right(), Point::new(3.0, 1.0));
+        assert_points_eq!(arc.reverse().point_at(0.25), arc.point_at(0.75));
+    }
+
+    #[test]
+    fn length() {
+        let circle = Ellipse::new(Point::zero(), Size::square(2.0));
+        let arc = EllipseArc::new(circle, Angle::FRAC_PI_2(), -Angle::PI());
+        assert_approx_eq!(arc.length(), std::f32::consts::PI * 3.0);
+        assert_approx_eq!(arc.t_at_length(std::f32::consts::PI), 1.0 / 3.0);
+        let ellipse = EllipseArc::new(
+            Ellipse::new(Point::zero(), Size::new(3.0, 1.0)),
+            Angle::ZERO(),
+            Angle::FRAC_PI_2(),
+        );
+        let middle = ellipse.point_at_length(ellipse.length() / 2.0);
+        // The arc covers more distance per turn near its end, so its middle by length comes later
+        // than its middle by `t`.
+        assert!(middle.x < ellipse.point_at(0.5).x);
+    }
+
+    #[test]
+    fn to_cubic_beziers() {
+        let arc = EllipseArc::new(
+            Ellipse::new(Point::new(5.0, 5.0), Size::new(4.0, 2.0)),
+            Angle::FRAC_PI_4(),
+            Angle::from_degrees(-200.0),
+        );
+        let curves = arc.to_cubic_beziers();
+        assert_eq!(curves.len(), 3);
+        assert_points_eq!(curves[0].from, arc.from());
+        assert_points_eq!(curves[2].to, arc.to());
+        for curve in curves {
+            for index in 0..=10 {
+                let point = curve.point_at(index as f32 / 10.0);
+                let offset = (point - Point::new(5.0, 5.0)) / Vector::new(4.0, 2.0);
+                assert!((offset.magnitude() - 1.0).abs() < 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn transform() {
+        let arc = EllipseArc::new(
+            Ellipse::new(Point::new(1.0, 1.0), Size::new(2.0, 1.0)),
+            Angle::FRAC_PI_4(),
+            Angle::FRAC_3PI_4(),
+        );
+        let transform = Transform::from_scale(-2.0, 3.0).post_translate(1.0, 0.0);
+        let transformed = arc.transform(transform).unwrap();
+        for t in [0.0, 0.4, 1.0] {
+            assert_points_eq!(
+                transformed.point_at(t),
+                arc.point_at(t).transform(transform),
+            );
+        }
+        let rotation = Transform::from_rotation(Angle::FRAC_PI_4(), Point::zero());
+        assert_eq!(arc.transform(rotation), None);
+    }
+
+    #[test]
+    fn flatten() {
+        let arc = EllipseArc::new(
+            Ellipse::new(Point::new(5.0, 5.0), Size::new(40.0, 10.0)),
+            Angle::FRAC_PI_4(),
+            Angle::from_degrees(-200.0),
+        );
+        let polyline = arc.flatten(0.05);
+        assert_eq!(polyline.points().first(), Some(&arc.from()));
+        assert_eq!(polyline.points().last(), Some(&arc.to()));
+        for index in 0..=1000 {
+            let point = arc.point_at(index as f32 / 1000.0);
+            assert!(polyline.distance_to(point) <= 0.05);
+        }
+    }
+}
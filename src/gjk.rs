@@ -0,0 +1,441 @@
+use crate::{
+    Capsule, Circle, Ellipse, LineSegment, Penetration, Point, Polygon, Quad, Rect, Vector,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const MAX_ITERATIONS: usize = 64;
+
+/// The nearest points between two shapes that don't overlap.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Separation<T = f32> {
+    /// The point on the first shape that's nearest to the second shape.
+    pub point: Point<T>,
+    /// The point on the second shape that's nearest to the first shape.
+    pub other_point: Point<T>,
+    /// The distance between the two points.
+    pub distance: T,
+}
+
+/// How two convex shapes are positioned relative to each other. Shapes that only touch are
+/// `Separated` by a distance of zero.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Contact<T = f32> {
+    Separated(Separation<T>),
+    Penetrating(Penetration<T>),
+}
+
+impl<T: en::Float> Contact<T> {
+    /// Returns the distance between the shapes, which is negative when they overlap.
+    pub fn distance(&self) -> T {
+        match self {
+            Self::Separated(separation) => separation.distance,
+            Self::Penetrating(penetration) => -penetration.depth,
+        }
+    }
+
+    pub fn separation(&self) -> Option<Separation<T>> {
+        match self {
+            Self::Separated(separation) => Some(*separation),
+            Self::Penetrating(_) => None,
+        }
+    }
+
+    pub fn penetration(&self) -> Option<Penetration<T>> {
+        match self {
+            Self::Separated(_) => None,
+            Self::Penetrating(penetration) => Some(*penetration),
+        }
+    }
+}
+
+/// A convex shape described by its support function. That's all the GJK and EPA algorithms need
+/// to find how far apart two shapes are or how deeply they overlap, so implementing this for a new
+/// shape lets it interact with every other shape.
+pub trait Support<T: en::Float> {
+    /// Returns the point of the shape that's farthest along `direction`, which is never zero but
+    /// isn't necessarily normalized.
+    fn support(&self, direction: Vector<T>) -> Point<T>;
+
+    /// Finds the nearest points between the shapes if they're apart, or how far they need to move
+    /// to come apart if they overlap.
+    fn contact(&self, other: &impl Support<T>) -> Contact<T>
+    where
+        Self: Sized,
+    {
+        let support = |direction: Vector<T>| Vertex::new(self, other, direction);
+        let mut simplex = vec![support(Vector::from_dx(T::one()))];
+        let mut scale = simplex[0].point.magnitude_squared();
+        let mut nearest = Nearest::vertex(simplex[0]);
+        for _ in 0..MAX_ITERATIONS {
+            nearest = match Nearest::reduce(&mut simplex) {
+                Some(nearest) => nearest,
+                None => return penetration(simplex, support, scale),
+            };
+            let vector = nearest.vector();
+            let distance_squared = vector.magnitude_squared();
+            // Once the origin is on the simplex, the shapes are at least touching.
+            if distance_squared <= T::epsilon() * scale {
+                return penetration(simplex, support, scale);
+            }
+            // Stop once the next support point doesn't get any closer to the origin.
+            let next = support(-vector);
+            if distance_squared - vector.dot_product(next.point)
+                <= tolerance::<T>() * distance_squared
+                || simplex.iter().any(|vertex| vertex.point == next.point)
+            {
+                break;
+            }
+            let magnitude_squared = next.point.magnitude_squared();
+            if magnitude_squared > scale {
+                scale = magnitude_squared;
+            }
+            simplex.push(next);
+        }
+        Contact::Separated(nearest.separation())
+    }
+}
+
+fn tolerance<T: en::Float>() -> T {
+    T::epsilon() * en::cast(16)
+}
+
+/// A point on the boundary of the Minkowski difference of two shapes, along with the points on
+/// each shape that produced it.
+#[derive(Clone, Copy, Debug)]
+struct Vertex<T> {
+    a: Point<T>,
+    b: Point<T>,
+    point: Vector<T>,
+}
+
+impl<T: en::Float> Vertex<T> {
+    fn new(a: &impl Support<T>, b: &impl Support<T>, direction: Vector<T>) -> Self {
+        let a = a.support(direction);
+        let b = b.support(-direction);
+        Self { a, b, point: a - b }
+    }
+}
+
+/// The point `t` of the way along the edge between two vertices that's nearest to the origin.
+#[derive(Clone, Copy, Debug)]
+struct Nearest<T> {
+    from: Vertex<T>,
+    to: Vertex<T>,
+    t: T,
+}
+
+impl<T: en::Float> Nearest<T> {
+    fn vertex(vertex: Vertex<T>) -> Self {
+        Self {
+            from: vertex,
+            to: vertex,
+            t: T::zero(),
+        }
+    }
+
+    fn edge(from: Vertex<T>, to: Vertex<T>) -> Self {
+        let edge = to.point - from.point;
+        let length_squared = edge.magnitude_squared();
+        let t = if length_squared == T::zero() {
+            T::zero()
+        } else {
+            -from.point.dot_product(edge) / length_squared
+        };
+        if t <= T::zero() {
+            Self::vertex(from)
+        } else if t >= T::one() {
+            Self::vertex(to)
+        } else {
+            Self { from, to, t }
+        }
+    }
+
+    /// Finds the point of the simplex nearest to the origin and drops any vertices that don't
+    /// contribute to it, or returns `None` if the simplex is a triangle containing the origin.
+    fn reduce(simplex: &mut Vec<Vertex<T>>) -> Option<Self> {
+        let nearest = match *simplex.as_slice() {
+            [vertex] => Self::vertex(vertex),
+            [from, to] => Self::edge(from, to),
+            [a, b, c] => {
+                let sides = [(a, b), (b, c), (c, a)]
+                    .map(|(from, to)| (to.point - from.point).cross_product(-from.point));
+                let area = (b.point - a.point).cross_product(c.point - a.point);
+                if area != T::zero()
+                    && (sides.iter().all(|side| *side >= T::zero())
+                        || sides.iter().all(|side| *side <= T::zero()))
+                {
+                    return None;
+                }
+                [Self::edge(a, b), Self::edge(b, c), Self::edge(c, a)]
+                    .into_iter()
+                    .fold(None, |best: Option<Self>, nearest| match best {
+                        Some(best)
+                            if best.vector().magnitude_squared()
+                                <= nearest.vector().magnitude_squared() =>
+                        {
+                            Some(best)
+                        }
+                        _ => Some(nearest),
+                    })
+                    .unwrap()
+            }
+            _ => unreachable!("simplices have between 1 and 3 vertices"),
+        };
+        simplex.clear();
+        simplex.push(nearest.from);
+        if nearest.t != T::zero() {
+            simplex.push(nearest.to);
+        }
+        Some(nearest)
+    }
+
+    fn vector(&self) -> Vector<T> {
+        self.from.point + (self.to.point - self.from.point) * self.t
+    }
+
+    fn separation(&self) -> Separation<T> {
+        let point = self.from.a + (self.to.a - self.from.a) * self.t;
+        let other_point = self.from.b + (self.to.b - self.from.b) * self.t;
+        Separation {
+            point,
+            other_point,
+            distance: (other_point - point).magnitude(),
+        }
+    }
+}
+
+/// Uses the expanding polytope algorithm to find the penetration of shapes whose Minkowski
+/// difference contains the origin somewhere in `simplex`.
+fn penetration<T: en::Float>(
+    mut polytope: Vec<Vertex<T>>,
+    support: impl Fn(Vector<T>) -> Vertex<T>,
+    scale: T,
+) -> Contact<T> {
+    let touching = |nearest: Nearest<T>| {
+        let mut separation = nearest.separation();
+        separation.distance = T::zero();
+        Contact::Separated(separation)
+    };
+    // The polytope needs to start out as a triangle, so a point or edge touching the origin has to
+    // be grown outwards first. If it can't be grown, the shapes have no area where they meet.
+    while polytope.len() < 3 {
+        let directions = match *polytope.as_slice() {
+            [_] => vec![
+                Vector::from_dx(T::one()),
+                Vector::from_dx(-T::one()),
+                Vector::from_dy(T::one()),
+                Vector::from_dy(-T::one()),
+            ],
+            [from, to] => {
+                let edge = to.point - from.point;
+                vec![
+                    Vector::new(edge.dy, -edge.dx),
+                    Vector::new(-edge.dy, edge.dx),
+                ]
+            }
+            _ => unreachable!("simplices have at least 1 vertex"),
+        };
+        let grown = directions.into_iter().map(&support).find(|vertex| {
+            let from = polytope[0].point;
+            match polytope.get(1) {
+                Some(to) => (to.point - from).cross_product(vertex.point - from) != T::zero(),
+                None => vertex.point != from,
+            }
+        });
+        match grown {
+            Some(vertex) => polytope.push(vertex),
+            None => {
+                return touching(match *polytope.as_slice() {
+                    [vertex] => Nearest::vertex(vertex),
+                    [from, to] => Nearest::edge(from, to),
+                    _ => unreachable!(),
+                })
+            }
+        }
+    }
+    // Wind the polytope so that each edge's outward normal is `(dy, -dx)`.
+    if (polytope[1].point - polytope[0].point).cross_product(polytope[2].point - polytope[0].point)
+        < T::zero()
+    {
+        polytope.swap(1, 2);
+    }
+    let threshold = tolerance::<T>() * scale.sqrt();
+    let mut closest = None;
+    for _ in 0..MAX_ITERATIONS {
+        // Find the edge nearest to the origin, and push it outwards.
+        closest = None;
+        for index in 0..polytope.len() {
+            let (from, to) = (polytope[index], polytope[(index + 1) % polytope.len()]);
+            let edge = to.point - from.point;
+            if edge == Vector::zero() {
+                continue;
+            }
+            let normal = Vector::new(edge.dy, -edge.dx).normalize();
+            let distance = normal.dot_product(from.point);
+            match closest {
+                Some((_, _, closest)) if closest <= distance => (),
+                _ => closest = Some((index, normal, distance)),
+            }
+        }
+        let (index, normal, distance) = match closest {
+            Some(closest) => closest,
+            None => break,
+        };
+        let next = support(normal);
+        if normal.dot_product(next.point) - distance <= threshold {
+            break;
+        }
+        polytope.insert(index + 1, next);
+    }
+    match closest {
+        Some((_, normal, depth)) if depth > threshold => {
+            Contact::Penetrating(Penetration { normal, depth })
+        }
+        Some((index, _, _)) => touching(Nearest::edge(
+            polytope[index],
+            polytope[(index + 1) % polytope.len()],
+        )),
+        None => touching(Nearest::vertex(polytope[0])),
+    }
+}
+
+fn farthest<T: en::Float>(
+    points: impl IntoIterator<Item = Point<T>>,
+    direction: Vector<T>,
+) -> Point<T> {
+    points
+        .into_iter()
+        .fold(None, |best: Option<(Point<T>, T)>, point| {
+            let distance = point.to_vector().dot_product(direction);
+            match best {
+                Some((_, farthest)) if farthest >= distance => best,
+                _ => Some((point, distance)),
+            }
+        })
+        .map(|(point, _)| point)
+        .unwrap_or_else(Point::zero)
+}
+
+impl<T: en::Float> Support<T> for Rect<T> {
+    fn support(&self, direction: Vector<T>) -> Point<T> {
+        Point::new(
+            if direction.dx > T::zero() {
+                self.right()
+            } else {
+                self.left()
+            },
+            if direction.dy > T::zero() {
+                self.bottom()
+            } else {
+                self.top()
+            },
+        )
+    }
+}
+
+impl<T: en::Float> Support<T> for Quad<T> {
+    fn support(&self, direction: Vector<T>) -> Point<T> {
+        farthest([self.a, self.b, self.c, self.d], direction)
+    }
+}
+
+/// Non-convex polygons act like their convex hull.
+impl<T: en::Float> Support<T> for Polygon<T> {
+    fn support(&self, direction: Vector<T>) -> Point<T> {
+        farthest(self.points().iter().copied(), direction)
+    }
+}
+
+impl<T: en::Float> Support<T> for LineSegment<T> {
+    fn support(&self, direction: Vector<T>) -> Point<T> {
+        farthest(self.points(), direction)
+    }
+}
+
+impl<T: en::Float> Support<T> for Circle<T> {
+    fn support(&self, direction: Vector<T>) -> Point<T> {
+        self.center() + direction.normalize() * self.radius()
+    }
+}
+
+impl<T: en::Float> Support<T> for Ellipse<T> {
+    fn support(&self, direction: Vector<T>) -> Point<T> {
+        let radius = self.radius();
+        let length = (direction.dx * radius.width()).hypot(direction.dy * radius.height());
+        if length == T::zero() {
+            return self.center();
+        }
+        let offset = Vector::new(
+            direction.dx * radius.width() * radius.width(),
+            direction.dy * radius.height() * radius.height(),
+        );
+        self.center() + offset / length
+    }
+}
+
+impl<T: en::Float> Support<T> for Capsule<T> {
+    fn support(&self, direction: Vector<T>) -> Point<T> {
+        self.segment.support(direction) + direction.normalize() * self.radius
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, test::rect, Collider, Size};
+
+    #[test]
+    fn separated() {
+        let separation = rect(0.0, 0.0, 2.0, 2.0)
+            .contact(&rect(5.0, 1.0, 2.0, 2.0))
+            .separation()
+            .unwrap();
+        assert_approx_eq!(separation.distance, 3.0);
+        assert_approx_eq!(separation.point.x, 2.0);
+        assert_approx_eq!(separation.other_point.x, 5.0);
+
+        let capsule = Capsule::from_points(Point::new(-3.0, 0.0), Point::new(3.0, 0.0), 1.0);
+        let circle = Circle::new(Point::new(6.0, 4.0), 1.0);
+        let separation = capsule.contact(&circle).separation().unwrap();
+        assert_approx_eq!(separation.distance, 3.0);
+        // The nearest points on curves converge slower than the distance between them.
+        assert!((separation.point - Point::new(3.6, 0.8)).magnitude() < 0.001);
+        assert!((separation.other_point - Point::new(5.4, 3.2)).magnitude() < 0.001);
+
+        let ellipse = Ellipse::new(Point::new(0.0, 0.0), Size::new(4.0, 1.0));
+        let segment = LineSegment::new(Point::new(-1.0, 3.0), Point::new(6.0, 3.0));
+        assert_approx_eq!(ellipse.contact(&segment).distance(), 2.0);
+    }
+
+    #[test]
+    fn touching() {
+        let contact = rect(0.0, 0.0, 2.0, 2.0).contact(&rect(2.0, 1.0, 2.0, 2.0));
+        assert_eq!(contact.distance(), 0.0);
+        assert!(contact.separation().is_some());
+    }
+
+    #[test]
+    fn penetrating() {
+        let a = rect(0.0, 0.0, 4.0, 4.0);
+        let b = rect(3.0, 1.0, 4.0, 4.0);
+        let penetration = a.contact(&b).penetration().unwrap();
+        assert_approx_eq!(penetration.normal.dx, 1.0);
+        assert_approx_eq!(penetration.normal.dy, 0.0);
+        assert_approx_eq!(penetration.depth, 1.0);
+
+        // Concentric circles are the worst case, since every direction is equally deep.
+        let contact = Circle::new(Point::zero(), 2.0).contact(&Circle::new(Point::zero(), 1.0));
+        assert!((contact.distance() + 3.0f32).abs() < 0.01);
+
+        let circle = Circle::new(Point::new(5.0, 5.0), 2.0);
+        let penetration = a.contact(&circle).penetration().unwrap();
+        assert_approx_eq!(penetration.depth, a.penetration(&circle).unwrap().depth);
+        // Curved shapes get approximated, and the normal is much more sensitive to that than the
+        // depth is.
+        assert!((penetration.normal.dx - penetration.normal.dy).abs() < 0.01);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{Line, Point, Polygon, Quad, Side};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+
+/// Every point on or to the right of `line`, as seen on screen while looking along it. This makes
+/// the inside of a clockwise polygon the intersection of the half-planes along its edges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct HalfPlane<T = f32> {
+    pub line: Line<T>,
+}
+
+impl<T: en::Num> HalfPlane<T> {
+    pub fn new(line: Line<T>) -> Self {
+        Self { line }
+    }
+
+    /// Builds the half-plane to the right of the line running from `a` towards `b`.
+    pub fn from_points(a: Point<T>, b: Point<T>) -> Self {
+        Self::new(Line::from_points(a, b))
+    }
+}
+
+impl<T: en::Float> HalfPlane<T> {
+    /// Returns the half-plane on the other side of the line. Both include the line itself.
+    pub fn flip(self) -> Self {
+        Self::new(Line::new(self.line.point, -self.line.vector))
+    }
+
+    pub fn contains(&self, point: Point<T>) -> bool {
+        self.line.side(point) != Side::Left
+    }
+
+    /// Keeps the part of `polygon` that's inside the half-plane, using the Sutherland-Hodgman
+    /// algorithm. This is only guaranteed to produce a single simple polygon if `polygon` is convex;
+    /// otherwise, separate pieces are joined by edges running along the line.
+    pub fn clip_polygon(&self, polygon: &Polygon<T>) -> Polygon<T> {
+        let mut clipped = Vec::with_capacity(polygon.len() + 1);
+        let mut any_inside = false;
+        for edge in polygon.line_segments() {
+            // Using the same exact test as `contains` keeps the two from disagreeing.
+            let (from, to) = (self.line.side(edge.from), self.line.side(edge.to));
+            any_inside |= from == Side::Right;
+            if from != Side::Left {
+                clipped.push(edge.from);
+            }
+            if (from == Side::Right && to == Side::Left)
+                || (from == Side::Left && to == Side::Right)
+            {
+                // The ends are on opposite sides, so this is the usual `from / (from - to)`, just
+                // without letting rounding in the distances push the crossing off the edge.
+                let from = self.line.signed_distance(edge.from).abs();
+                let total = from + self.line.signed_distance(edge.to).abs();
+                let factor = if total > T::zero() {
+                    from / total
+                } else {
+                    T::zero()
+                };
+                clipped.push(edge.from + edge.vector() * factor);
+            }
+        }
+        // Without any vertex strictly inside, all that's left lies along the line.
+        if !any_inside {
+            clipped.clear();
+        }
+        Polygon::new(clipped)
+    }
+
+    pub fn clip_quad(&self, quad: &Quad<T>) -> Polygon<T> {
+        self.clip_polygon(&Polygon::from(*quad))
+    }
+}
+
+impl<T: en::Float> Polygon<T> {
+    /// Keeps the part of the polygon that's inside every half-plane, e.g. to find what's visible
+    /// within a vision cone.
+    pub fn clip_to_half_planes<I>(&self, half_planes: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<HalfPlane<T>>,
+    {
+        half_planes
+            .into_iter()
+            .fold(self.clone(), |polygon, half_plane| {
+                half_plane.borrow().clip_polygon(&polygon)
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, Rect, Size, Vector};
+
+    fn square() -> Polygon {
+        Polygon::from(Rect::from_top_left(Point::zero(), Size::new(4.0, 4.0)))
+    }
+
+    #[test]
+    fn contains() {
+        // Looking right, everything below the line is inside.
+        let half_plane = HalfPlane::from_points(Point::new(0.0, 1.0), Point::new(1.0, 1.0));
+        assert!(half_plane.contains(Point::new(5.0, 3.0)));
+        assert!(half_plane.contains(Point::new(-5.0, 1.0)));
+        assert!(!half_plane.contains(Point::new(0.0, 0.0)));
+        assert!(half_plane.flip().contains(Point::new(0.0, 0.0)));
+    }
+
+    #[test]
+    fn clip_polygon() {
+        let half_plane = HalfPlane::from_points(Point::new(0.0, 1.0), Point::new(1.0, 1.0));
+        let clipped = half_plane.clip_polygon(&square());
+        assert_approx_eq!(clipped.area(), 12.0);
+        assert!(clipped.points().iter().all(|point| point.y >= 1.0));
+        // Everything is outside, apart from the edge along the line.
+        assert!(half_plane.flip().clip_polygon(&clipped).is_empty());
+        // Everything is inside.
+        assert_eq!(half_plane.clip_polygon(&clipped), clipped);
+    }
+
+    #[test]
+    fn clip_nearly_on_the_line() {
+        let half_plane: HalfPlane =
+            HalfPlane::new(Line::new(Point::new(0.1, 0.3), Vector::new(0.7, 0.9)));
+        // Rounding puts this exactly on the line, but it's just outside.
+        let outside = Point::new(0.1822, 0.40568572);
+        assert_eq!(half_plane.line.signed_distance(outside), 0.0);
+        assert!(!half_plane.contains(outside));
+        let triangle = Polygon::new(vec![outside, Point::new(0.5, 0.3), Point::new(0.9, 0.5)]);
+        assert!(half_plane.clip_polygon(&triangle).is_empty());
+    }
+
+    #[test]
+    fn clip_quad() {
+        let quad = Quad {
+            a: Point::new(2.0, 0.0),
+            b: Point::new(4.0, 2.0),
+            c: Point::new(2.0, 4.0),
+            d: Point::new(0.0, 2.0),
+        };
+        let half_plane = HalfPlane::new(Line::new(Point::new(2.0, 0.0), Vector::new(0.0, 1.0)));
+        assert_approx_eq!(half_plane.clip_quad(&quad).area(), 4.0);
+    }
+
+    #[test]
+    fn vision_cone() {
+        let eye = Point::new(0.0, 2.0);
+        let cone = [
+            HalfPlane::from_points(eye, Point::new(4.0, 0.0)),
+            HalfPlane::from_points(Point::new(4.0, 4.0), eye),
+        ];
+        let visible = square().clip_to_half_planes(cone);
+        assert_approx_eq!(visible.area(), 8.0);
+    }
+}
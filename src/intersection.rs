@@ -0,0 +1,349 @@
+use crate::{orient2d, LineSegment, Point, Ray, Vector};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Where two segments or rays meet.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Intersection<T = f32> {
+    /// They don't meet at all, which includes lines that are parallel but not collinear.
+    None,
+    /// They cross or touch at a single point.
+    Point(Point<T>),
+    /// They're collinear and share this stretch, which points the same way as the first one.
+    Overlap(LineSegment<T>),
+    /// They're collinear rays pointing the same way, so they share a whole ray.
+    Ray(Ray<T>),
+}
+
+impl<T> Intersection<T> {
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    pub fn is_some(&self) -> bool {
+        !self.is_none()
+    }
+
+    /// Returns the single point where they meet, or `None` if they don't meet or overlap.
+    pub fn point(self) -> Option<Point<T>> {
+        match self {
+            Self::Point(point) => Some(point),
+            _ => None,
+        }
+    }
+}
+
+/// One of the two inputs: the points `origin + vector * t` for `t` in `min..=max`, where `max` is
+/// infinite for rays.
+#[derive(Clone, Copy, Debug)]
+struct Span<T> {
+    origin: Point<T>,
+    vector: Vector<T>,
+    max: T,
+}
+
+impl<T: en::Float> Span<T> {
+    fn line_segment(segment: LineSegment<T>) -> Self {
+        Self {
+            origin: segment.from,
+            vector: segment.vector(),
+            max: T::one(),
+        }
+    }
+
+    fn ray(ray: Ray<T>) -> Self {
+        Self {
+            origin: ray.point,
+            vector: ray.unit_vector(),
+            max: T::infinity(),
+        }
+    }
+
+    fn at(&self, t: T) -> Point<T> {
+        self.origin + self.vector * t
+    }
+
+    fn contains(&self, t: T) -> bool {
+        t >= T::zero() && t <= self.max
+    }
+
+    fn clamp(&self, t: T) -> T {
+        if t < T::zero() {
+            T::zero()
+        } else if t > self.max {
+            self.max
+        } else {
+            t
+        }
+    }
+}
+
+/// Checks if `a` and `b` are parallel, allowing for the rounding error in a ray's unit vector,
+/// which is never perfectly axis-aligned when it points up or down.
+fn parallel<T: en::Float>(a: Vector<T>, b: Vector<T>) -> bool {
+    a.cross_product(b).abs() <= T::epsilon() * en::cast(4) * a.magnitude() * b.magnitude()
+}
+
+/// Finds where two collinear spans overlap. `a` must have some length.
+fn overlap<T: en::Float>(a: Span<T>, b: Span<T>) -> Intersection<T> {
+    // Find where `b` starts and ends along `a`.
+    let length_squared = a.vector.magnitude_squared();
+    let start = (b.origin - a.origin).dot_product(a.vector) / length_squared;
+    let direction = b.vector.dot_product(a.vector) / length_squared;
+    let end = if b.max.is_finite() {
+        start + direction * b.max
+    } else if direction > T::zero() {
+        T::infinity()
+    } else {
+        T::neg_infinity()
+    };
+    let (start, end) = if start <= end {
+        (start, end)
+    } else {
+        (end, start)
+    };
+    let (start, end) = (en::Num::max(start, T::zero()), en::Num::min(end, a.max));
+    if start > end {
+        Intersection::None
+    } else if start == end {
+        Intersection::Point(a.at(start))
+    } else if end.is_infinite() {
+        Intersection::Ray(Ray::new(a.at(start), a.vector.angle()))
+    } else {
+        Intersection::Overlap(LineSegment::new(a.at(start), a.at(end)))
+    }
+}
+
+/// Finds the point where two spans that aren't parallel cross. If `clamp` is set, they're already
+/// known to meet, so rounding error mustn't turn that into a miss.
+fn crossing<T: en::Float>(a: Span<T>, b: Span<T>, clamp: bool) -> Intersection<T> {
+    let offset = b.origin - a.origin;
+    let denominator = a.vector.cross_product(b.vector);
+    let t = offset.cross_product(b.vector) / denominator;
+    let u = offset.cross_product(a.vector) / denominator;
+    let (t, u) = if clamp {
+        (a.clamp(t), b.clamp(u))
+    } else if a.contains(t) && b.contains(u) {
+        (t, u)
+    } else {
+        return Intersection::None;
+    };
+    // Take coordinates straight from any axis-aligned input, so that e.g. a vertical segment's
+    // intersections all have exactly the same x.
+    let (from_a, from_b) = (a.at(t), b.at(u));
+    let pick = |a_fixed: bool, b_fixed: bool, a: T, b: T, a_origin: T, b_origin: T| {
+        if a_fixed {
+            a_origin
+        } else if b_fixed {
+            b_origin
+        } else {
+            a.halved() + b.halved()
+        }
+    };
+    Intersection::Point(Point::new(
+        pick(
+            a.vector.dx == T::zero(),
+            b.vector.dx == T::zero(),
+            from_a.x,
+            from_b.x,
+            a.origin.x,
+            b.origin.x,
+        ),
+        pick(
+            a.vector.dy == T::zero(),
+            b.vector.dy == T::zero(),
+            from_a.y,
+            from_b.y,
+            a.origin.y,
+            b.origin.y,
+        ),
+    ))
+}
+
+impl<T: en::Float> LineSegment<T> {
+    pub fn intersection(&self, other: &LineSegment<T>) -> Intersection<T> {
+        let orient =
+            |segment: &LineSegment<T>, point: Point<T>| orient2d(segment.from, segment.to, point);
+        let sides = [
+            orient(self, other.from),
+            orient(self, other.to),
+            orient(other, self.from),
+            orient(other, self.to),
+        ];
+        let straddles = |from: f64, to: f64| !(from < 0.0 && to < 0.0 || from > 0.0 && to > 0.0);
+        if !straddles(sides[0], sides[1]) || !straddles(sides[2], sides[3]) {
+            return Intersection::None;
+        }
+        let (a, b) = (Span::line_segment(*self), Span::line_segment(*other));
+        // Only exactly collinear segments can overlap. Anything else that gets this far meets at
+        // exactly one point, no matter how close to parallel the segments are.
+        if sides.iter().all(|side| *side == 0.0) {
+            return match (a.vector == Vector::zero(), b.vector == Vector::zero()) {
+                (true, true) if a.origin == b.origin => Intersection::Point(a.origin),
+                (true, true) => Intersection::None,
+                (true, false) => overlap(b, a),
+                (false, _) => overlap(a, b),
+            };
+        }
+        if a.vector.cross_product(b.vector) == T::zero() {
+            // Too close to parallel to divide by, so they must meet at whichever endpoint is
+            // closest to being on the other segment.
+            let endpoints = [other.from, other.to, self.from, self.to];
+            let closest = (0..4)
+                .min_by(|i, j| {
+                    sides[*i]
+                        .abs()
+                        .partial_cmp(&sides[*j].abs())
+                        .unwrap_or(Ordering::Equal)
+                })
+                .unwrap();
+            return Intersection::Point(endpoints[closest]);
+        }
+        crossing(a, b, true)
+    }
+}
+
+impl<T: en::Float> Ray<T> {
+    pub fn intersection(&self, other: Ray<T>) -> Intersection<T> {
+        intersect(Span::ray(*self), Span::ray(other))
+    }
+
+    pub fn line_segment_intersection(&self, line_segment: LineSegment<T>) -> Intersection<T> {
+        intersect(Span::ray(*self), Span::line_segment(line_segment))
+    }
+}
+
+/// Intersects a ray with a ray or a segment, which has to allow for the rounding error in the ray's
+/// unit vector when deciding if they're parallel.
+fn intersect<T: en::Float>(a: Span<T>, b: Span<T>) -> Intersection<T> {
+    if parallel(a.vector, b.vector) {
+        if parallel(b.origin - a.origin, a.vector) {
+            overlap(a, b)
+        } else {
+            Intersection::None
+        }
+    } else {
+        crossing(a, b, false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, test::segment, Angle};
+
+    #[test]
+    fn line_segments() {
+        let a = segment(0.0, 0.0, 4.0, 4.0);
+        assert_eq!(
+            a.intersection(&segment(0.0, 4.0, 4.0, 0.0)),
+            Intersection::Point(Point::new(2.0, 2.0))
+        );
+        assert!(a.intersection(&segment(0.0, 4.0, 1.0, 3.0)).is_none());
+        // Touching at an endpoint.
+        assert_eq!(
+            a.intersection(&segment(4.0, 4.0, 8.0, 0.0)),
+            Intersection::Point(Point::new(4.0, 4.0))
+        );
+        // Parallel, but not collinear.
+        assert!(a.intersection(&segment(1.0, 0.0, 5.0, 4.0)).is_none());
+    }
+
+    #[test]
+    fn axis_aligned() {
+        let horizontal = segment(0.0, 0.3, 1.0, 0.3);
+        let vertical = segment(0.7, -1.0, 0.7, 1.0);
+        assert_eq!(
+            horizontal.intersection(&vertical),
+            Intersection::Point(Point::new(0.7, 0.3))
+        );
+        let slanted = segment(0.1, -0.9, 0.9, 0.7);
+        let point = vertical.intersection(&slanted).point().unwrap();
+        assert_eq!(point.x, 0.7);
+    }
+
+    #[test]
+    fn nearly_touching() {
+        let diagonal = segment(0.0, 0.0, 1.0, 1.0);
+        let above = f32::from_bits(0.3f32.to_bits() + 1);
+        assert!(diagonal
+            .intersection(&segment(0.3, above, 0.3, 5.0))
+            .is_none());
+        assert_eq!(
+            diagonal.intersection(&segment(0.3, 0.3, 0.3, 5.0)),
+            Intersection::Point(Point::new(0.3, 0.3))
+        );
+    }
+
+    #[test]
+    fn nearly_parallel() {
+        let long = segment(0.0, 0.0, 1000.0, 0.0);
+        // This only touches `long` at its start, even though it's very nearly collinear.
+        assert_eq!(
+            long.intersection(&segment(500.0, 0.0, 1000.0, 1e-4)),
+            Intersection::Point(Point::new(500.0, 0.0))
+        );
+        // This crosses `long` in the middle at a very shallow angle.
+        let shallow = segment(0.0, -1e-4, 1000.0, 1e-4);
+        for point in [long.intersection(&shallow), shallow.intersection(&long)] {
+            let point = point.point().unwrap();
+            assert!((point.x - 500.0).abs() < 0.001);
+            assert_eq!(point.y, 0.0);
+        }
+    }
+
+    #[test]
+    fn collinear() {
+        let a = segment(0.0, 0.0, 4.0, 0.0);
+        assert_eq!(
+            a.intersection(&segment(6.0, 0.0, 2.0, 0.0)),
+            Intersection::Overlap(segment(2.0, 0.0, 4.0, 0.0))
+        );
+        assert_eq!(
+            a.intersection(&segment(4.0, 0.0, 6.0, 0.0)),
+            Intersection::Point(Point::new(4.0, 0.0))
+        );
+        assert!(a.intersection(&segment(5.0, 0.0, 6.0, 0.0)).is_none());
+        // A segment with no length is just a point.
+        assert_eq!(
+            a.intersection(&segment(1.0, 0.0, 1.0, 0.0)),
+            Intersection::Point(Point::new(1.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn rays() {
+        let right = Ray::new(Point::zero(), Angle::ZERO());
+        let up = Ray::new(Point::new(2.0, 2.0), Angle::FRAC_PI_2());
+        let point = right.intersection(up).point().unwrap();
+        assert_approx_eq!(point.x, 2.0);
+        assert_approx_eq!(point.y, 0.0);
+        // Parallel rays used to produce NaN.
+        let above = Ray::new(Point::new(0.0, -1.0), Angle::ZERO());
+        assert!(right.intersection(above).is_none());
+
+        let left = Ray::new(Point::new(3.0, 0.0), Angle::PI());
+        match right.intersection(left) {
+            Intersection::Overlap(overlap) => {
+                assert_approx_eq!(overlap.length(), 3.0);
+            }
+            other => panic!("expected an overlap, got {:?}", other),
+        }
+        match right.intersection(Ray::new(Point::new(-2.0, 0.0), Angle::ZERO())) {
+            Intersection::Ray(ray) => assert_eq!(ray.point, Point::zero()),
+            other => panic!("expected a ray, got {:?}", other),
+        }
+
+        let vertical = segment(0.0, -1.0, 0.0, -4.0);
+        let hit = Ray::new(Point::zero(), Angle::FRAC_PI_2()).line_segment_intersection(vertical);
+        match hit {
+            Intersection::Overlap(overlap) => {
+                assert_approx_eq!(overlap.from.y, -1.0);
+                assert_approx_eq!(overlap.to.y, -4.0);
+            }
+            other => panic!("expected an overlap, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{Point, Rect};
+use std::{cmp::Ordering, iter::FromIterator};
+
+/// A static spatial index over points with values attached, for finding the points nearest to some
+/// other point. The tree is built all at once, and can't be changed afterwards.
+#[derive(Clone, Debug)]
+pub struct KdTree<V, T = f32> {
+    /// The tree is implicit: the median of each range is its root, with the points before and after
+    /// it making up its left and right subtrees. Splits alternate between x and y, starting with x.
+    items: Vec<(Point<T>, V)>,
+}
+
+fn coord<T: en::Float>(point: Point<T>, depth: usize) -> T {
+    if depth & 1 == 0 {
+        point.x
+    } else {
+        point.y
+    }
+}
+
+fn build<V, T: en::Float>(items: &mut [(Point<T>, V)], depth: usize) {
+    if items.len() > 1 {
+        let middle = items.len() / 2;
+        items.select_nth_unstable_by(middle, |(a, _), (b, _)| {
+            coord(*a, depth)
+                .partial_cmp(&coord(*b, depth))
+                .unwrap_or(Ordering::Equal)
+        });
+        let (left, right) = items.split_at_mut(middle);
+        build(left, depth + 1);
+        build(&mut right[1..], depth + 1);
+    }
+}
+
+impl<V, T: en::Float> KdTree<V, T> {
+    pub fn new(mut items: Vec<(Point<T>, V)>) -> Self {
+        build(&mut items, 0);
+        Self { items }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Iterates over every point, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<T>, &V)> + '_ {
+        self.items.iter().map(|(point, value)| (*point, value))
+    }
+
+    /// Returns the point nearest to `point`, or `None` if the tree is empty.
+    pub fn nearest(&self, point: Point<T>) -> Option<(Point<T>, &V)> {
+        self.k_nearest(point, 1).into_iter().next()
+    }
+
+    /// Returns the `k` points nearest to `point`, from nearest to farthest.
+    pub fn k_nearest(&self, point: Point<T>, k: usize) -> Vec<(Point<T>, &V)> {
+        self.k_nearest_within(point, k, T::infinity())
+    }
+
+    /// Returns up to `k` of the points nearest to `point` that are no more than `radius` away from
+    /// it, from nearest to farthest.
+    pub fn k_nearest_within(&self, point: Point<T>, k: usize, radius: T) -> Vec<(Point<T>, &V)> {
+        let mut nearest = Vec::with_capacity(k.min(self.items.len()) + 1);
+        if k > 0 {
+            self.search(
+                point,
+                k,
+                radius * radius,
+                0..self.items.len(),
+                0,
+                &mut nearest,
+            );
+        }
+        nearest
+            .into_iter()
+            .map(|(_, index)| {
+                let (point, value) = &self.items[index];
+                (*point, value)
+            })
+            .collect()
+    }
+
+    /// Finds every point that's no more than `radius` away from `point`, in no particular order.
+    pub fn query_radius(
+        &self,
+        point: Point<T>,
+        radius: T,
+    ) -> impl Iterator<Item = (Point<T>, &V)> + '_ {
+        self.query(
+            move |depth, split| {
+                let center = coord(point, depth);
+                (center - radius <= split, center + radius >= split)
+            },
+            move |candidate| (candidate - point).magnitude_squared() <= radius * radius,
+        )
+    }
+
+    /// Finds every point inside `rect`, including along its edges, in no particular order.
+    pub fn query_rect(&self, rect: Rect<T>) -> impl Iterator<Item = (Point<T>, &V)> + '_ {
+        self.query(
+            move |depth, split| {
+                (
+                    coord(rect.top_left(), depth) <= split,
+                    coord(rect.bottom_right(), depth) >= split,
+                )
+            },
+            move |candidate| rect.contains_inclusive(candidate),
+        )
+    }
+
+    /// Visits the ranges that could hold points passing `test`. `sides` decides whether to go
+    /// before and after a split at the given depth and coordinate.
+    fn query<'a>(
+        &'a self,
+        sides: impl Fn(usize, T) -> (bool, bool) + 'a,
+        test: impl Fn(Point<T>) -> bool + 'a,
+    ) -> impl Iterator<Item = (Point<T>, &'a V)> + 'a {
+        let mut stack = vec![(0, self.items.len(), 0)];
+        std::iter::from_fn(move || loop {
+            let (start, end, depth) = stack.pop()?;
+            if start == end {
+                continue;
+            }
+            let middle = start + (end - start) / 2;
+            let (point, value) = &self.items[middle];
+            let (before, after) = sides(depth, coord(*point, depth));
+            if before {
+                stack.push((start, middle, depth + 1));
+            }
+            if after {
+                stack.push((middle + 1, end, depth + 1));
+            }
+            if test(*point) {
+                return Some((*point, value));
+            }
+        })
+    }
+
+    /// Keeps the `k` nearest points found so far sorted in `nearest`, along with their squared
+    /// distances, and skips any subtrees that can't beat the farthest of them.
+    fn search(
+        &self,
+        point: Point<T>,
+        k: usize,
+        max_squared: T,
+        range: std::ops::Range<usize>,
+        depth: usize,
+        nearest: &mut Vec<(T, usize)>,
+    ) {
+        if range.is_empty() {
+            return;
+        }
+        let middle = range.start + range.len() / 2;
+        let candidate = self.items[middle].0;
+        let distance = (candidate - point).magnitude_squared();
+        let worst = |nearest: &Vec<(T, usize)>| {
+            if nearest.len() < k {
+                max_squared
+            } else {
+                nearest[k - 1].0
+            }
+        };
+        if distance <= worst(nearest) {
+            let position = nearest.partition_point(|(other, _)| *other <= distance);
+            nearest.insert(position, (distance, middle));
+            nearest.truncate(k);
+        }
+
+        let offset = coord(point, depth) - coord(candidate, depth);
+        let (near, far) = if offset < T::zero() {
+            (range.start..middle, middle + 1..range.end)
+        } else {
+            (middle + 1..range.end, range.start..middle)
+        };
+        self.search(point, k, max_squared, near, depth + 1, nearest);
+        if offset * offset <= worst(nearest) {
+            self.search(point, k, max_squared, far, depth + 1, nearest);
+        }
+    }
+}
+
+impl<V, T: en::Float> FromIterator<(Point<T>, V)> for KdTree<V, T> {
+    fn from_iter<I: IntoIterator<Item = (Point<T>, V)>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{test::sorted, Size};
+
+    /// A 10x10 grid of points, spaced 1 apart and labeled by their position.
+    fn grid() -> KdTree<(i32, i32)> {
+        (0..10)
+            .flat_map(|y| (0..10).map(move |x| (Point::new(x as f32, y as f32), (x, y))))
+            .collect()
+    }
+
+    #[test]
+    fn nearest() {
+        let tree = grid();
+        assert_eq!(tree.len(), 100);
+        assert_eq!(
+            tree.nearest(Point::new(3.2, 6.9)),
+            Some((Point::new(3.0, 7.0), &(3, 7)))
+        );
+        assert_eq!(
+            tree.nearest(Point::new(-50.0, 50.0)),
+            Some((Point::new(0.0, 9.0), &(0, 9)))
+        );
+        assert_eq!(KdTree::<()>::new(Vec::new()).nearest(Point::zero()), None);
+    }
+
+    #[test]
+    fn k_nearest() {
+        let tree = grid();
+        let nearest: Vec<_> = tree
+            .k_nearest(Point::new(4.1, 4.2), 3)
+            .into_iter()
+            .map(|(_, value)| *value)
+            .collect();
+        assert_eq!(nearest, vec![(4, 4), (4, 5), (5, 4)]);
+        assert_eq!(tree.k_nearest(Point::zero(), 1000).len(), 100);
+        // Only the corner point is close enough.
+        assert_eq!(
+            tree.k_nearest_within(Point::new(-0.5, -0.5), 5, 1.0).len(),
+            1
+        );
+        assert!(tree.k_nearest(Point::zero(), 0).is_empty());
+    }
+
+    #[test]
+    fn queries() {
+        let tree = grid();
+        assert_eq!(
+            sorted(tree.query_radius(Point::new(5.0, 5.0), 1.0)),
+            vec![(4, 5), (5, 4), (5, 5), (5, 6), (6, 5)]
+        );
+        assert_eq!(
+            sorted(tree.query_rect(Rect::from_top_left(
+                Point::new(7.5, 8.0),
+                Size::new(5.0, 1.0)
+            ))),
+            vec![(8, 8), (8, 9), (9, 8), (9, 9)]
+        );
+    }
+}
@@ -3,32 +3,69 @@
 #[macro_use]
 mod cast;
 
+mod aabb_tree;
 mod angle;
+mod bezier;
+mod boolean;
+mod capsule;
 mod circle;
+mod clip;
+mod collider;
+mod convex_hull;
+mod crossing;
+mod delaunay;
 mod direction;
+mod distance;
 mod ellipse;
+mod ellipse_arc;
+mod gjk;
+mod half_plane;
+mod intersection;
+mod kd_tree;
 mod lerp;
+mod line;
 mod line_segment;
+mod multi_polygon;
+mod offset;
+mod path;
 mod point;
+mod polygon;
+mod polygon_with_holes;
+mod polyline;
+mod predicates;
+mod quadtree;
 mod ray;
+mod raycast;
 mod rect;
 mod rect_position;
+mod rounded_rect;
+mod sdf;
 mod size;
+mod slide;
+mod spatial_hash;
 mod support;
+mod sweep;
 mod transform;
 mod transform3d;
+mod triangulate;
 mod vector;
 
 pub mod split;
 
 pub use self::{
-    angle::*, circle::*, direction::*, ellipse::*, lerp::*, line_segment::*, point::*, ray::*,
-    rect::*, rect_position::*, size::*, support::*, transform::*, transform3d::*, vector::*,
+    aabb_tree::*, angle::*, bezier::*, boolean::*, capsule::*, circle::*, collider::*, crossing::*,
+    delaunay::*, direction::*, distance::*, ellipse::*, ellipse_arc::*, gjk::*, half_plane::*,
+    intersection::*, kd_tree::*, lerp::*, line::*, line_segment::*, multi_polygon::*, offset::*,
+    path::*, point::*, polygon::*, polygon_with_holes::*, polyline::*, predicates::*, quadtree::*,
+    ray::*, raycast::*, rect::*, rect_position::*, rounded_rect::*, sdf::*, size::*, slide::*,
+    spatial_hash::*, support::*, sweep::*, transform::*, transform3d::*, triangulate::*, vector::*,
 };
 pub use en;
 
 #[cfg(test)]
 pub(crate) mod test {
+    use crate::{LineSegment, Point, Rect, Size};
+
     pub fn approx_eq(lhs: f32, rhs: f32) -> bool {
         lhs.is_finite() && rhs.is_finite() && ((lhs - 0.00001)..(lhs + 0.00001)).contains(&rhs)
     }
@@ -57,4 +94,37 @@ pub(crate) mod test {
             )
         }};
     }
+
+    #[macro_export]
+    macro_rules! assert_points_eq {
+        ($lhs:expr, $rhs:expr $(,)?) => {{
+            let left = $lhs;
+            let right = $rhs;
+            assert!(
+                $crate::test::approx_eq(left.x, right.x)
+                    && $crate::test::approx_eq(left.y, right.y),
+                "assertion failed: `(left ≈≈ right)`\n  left: `{:?}`,\n right: `{:?}`",
+                left,
+                right,
+            )
+        }};
+    }
+
+    pub fn rect(x: f32, y: f32, width: f32, height: f32) -> Rect {
+        Rect::from_top_left(Point::new(x, y), Size::new(width, height))
+    }
+
+    pub fn square(x: f32, y: f32, size: f32) -> Rect {
+        Rect::from_top_left(Point::new(x, y), Size::square(size))
+    }
+
+    pub fn segment(x1: f32, y1: f32, x2: f32, y2: f32) -> LineSegment {
+        LineSegment::new(Point::new(x1, y1), Point::new(x2, y2))
+    }
+
+    pub fn sorted<'a, H, V: Copy + Ord + 'a>(results: impl Iterator<Item = (H, &'a V)>) -> Vec<V> {
+        let mut values: Vec<V> = results.map(|(_, value)| *value).collect();
+        values.sort_unstable();
+        values
+    }
 }
@@ -0,0 +1,169 @@
+use crate::{orient2d, LineSegment, Point, Ray, Vector};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Which side of a line a point is on, as seen on screen (i.e. with y pointing down) while looking
+/// along the line.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Side {
+    Left,
+    On,
+    Right,
+}
+
+/// An infinite line through `point`, running along `vector`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Line<T = f32> {
+    pub point: Point<T>,
+    pub vector: Vector<T>,
+}
+
+impl<T: en::Num> Line<T> {
+    pub fn new(point: Point<T>, vector: Vector<T>) -> Self {
+        Self { point, vector }
+    }
+
+    /// Builds the line through `a` and `b`, running from `a` towards `b`.
+    pub fn from_points(a: Point<T>, b: Point<T>) -> Self {
+        Self::new(a, b - a)
+    }
+}
+
+impl<T: en::Float> Line<T> {
+    /// Builds the line of points where `a * x + b * y + c` is zero. Points where it's positive are
+    /// on the right. `a` and `b` can't both be zero.
+    pub fn from_implicit(a: T, b: T, c: T) -> Self {
+        let normal = Vector::new(a, b);
+        Self::new(
+            (normal * -c / normal.magnitude_squared()).to_point(),
+            Vector::new(b, -a),
+        )
+    }
+
+    /// Returns `(a, b, c)` such that `a * x + b * y + c` is the signed distance from the line, so
+    /// `(a, b)` is the unit normal pointing to the right.
+    pub fn implicit(&self) -> (T, T, T) {
+        let normal = self.normal();
+        (
+            normal.dx,
+            normal.dy,
+            -normal.dot_product(self.point.to_vector()),
+        )
+    }
+
+    /// The unit vector perpendicular to the line, pointing to the right.
+    pub fn normal(&self) -> Vector<T> {
+        self.vector.perpendicular().normalize()
+    }
+
+    /// Returns the distance from the line to `point`, which is positive on the right and negative
+    /// on the left.
+    pub fn signed_distance(&self, point: Point<T>) -> T {
+        self.vector.cross_product(point - self.point) / self.vector.magnitude()
+    }
+
+    pub fn distance(&self, point: Point<T>) -> T {
+        self.signed_distance(point).abs()
+    }
+
+    /// Finds which side of the line `point` is on. This uses `orient2d`, so it's exact for the line
+    /// through `self.point` and `self.point + self.vector`.
+    pub fn side(&self, point: Point<T>) -> Side {
+        let orientation = orient2d(self.point, self.point + self.vector, point);
+        if orientation > 0.0 {
+            Side::Right
+        } else if orientation < 0.0 {
+            Side::Left
+        } else {
+            Side::On
+        }
+    }
+
+    /// Returns the point on the line that's nearest to `point`.
+    pub fn project(&self, point: Point<T>) -> Point<T> {
+        let t = (point - self.point).dot_product(self.vector) / self.vector.magnitude_squared();
+        self.point + self.vector * t
+    }
+
+    /// Mirrors `point` across the line.
+    pub fn reflect(&self, point: Point<T>) -> Point<T> {
+        let projected = self.project(point);
+        projected + (projected - point)
+    }
+
+    /// Returns where the lines cross, or `None` if they're parallel (including if they're the same
+    /// line).
+    pub fn intersection(&self, other: &Line<T>) -> Option<Point<T>> {
+        let denominator = self.vector.cross_product(other.vector);
+        if denominator == T::zero() {
+            return None;
+        }
+        let t = (other.point - self.point).cross_product(other.vector) / denominator;
+        Some(self.point + self.vector * t)
+    }
+}
+
+impl<T: en::Num> From<LineSegment<T>> for Line<T> {
+    fn from(segment: LineSegment<T>) -> Self {
+        Self::from_points(segment.from, segment.to)
+    }
+}
+
+impl<T: en::Float> From<Ray<T>> for Line<T> {
+    fn from(ray: Ray<T>) -> Self {
+        Self::new(ray.point, ray.unit_vector())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn distance_and_side() {
+        let line = Line::from_points(Point::new(0.0, 1.0), Point::new(4.0, 1.0));
+        // Below the line is on the right, as seen on screen.
+        assert_approx_eq!(line.signed_distance(Point::new(2.0, 3.0)), 2.0);
+        assert_approx_eq!(line.signed_distance(Point::new(-5.0, -1.0)), -2.0);
+        assert_eq!(line.side(Point::new(2.0, 3.0)), Side::Right);
+        assert_eq!(line.side(Point::new(2.0, -1.0)), Side::Left);
+        assert_eq!(line.side(Point::new(9.0, 1.0)), Side::On);
+    }
+
+    #[test]
+    fn implicit() {
+        let line = Line::from_implicit(3.0, 4.0, -10.0);
+        assert_approx_eq!(line.signed_distance(Point::new(2.0, 1.0)), 0.0);
+        assert_approx_eq!(line.signed_distance(Point::new(5.0, 5.0)), 5.0);
+        let (a, b, c) = line.implicit();
+        assert_approx_eq!(a, 0.6);
+        assert_approx_eq!(b, 0.8);
+        assert_approx_eq!(c, -2.0);
+    }
+
+    #[test]
+    fn project_and_reflect() {
+        let line = Line::from_points(Point::new(0.0, 0.0), Point::new(1.0, 1.0));
+        let projected = line.project(Point::new(2.0, 0.0));
+        assert_approx_eq!(projected.x, 1.0);
+        assert_approx_eq!(projected.y, 1.0);
+        let reflected = line.reflect(Point::new(2.0, 0.0));
+        assert_approx_eq!(reflected.x, 0.0);
+        assert_approx_eq!(reflected.y, 2.0);
+    }
+
+    #[test]
+    fn intersection() {
+        let a = Line::from_points(Point::new(0.0, 0.0), Point::new(1.0, 1.0));
+        let b = Line::from_points(Point::new(4.0, 0.0), Point::new(3.0, 1.0));
+        let point = a.intersection(&b).unwrap();
+        assert_approx_eq!(point.x, 2.0);
+        assert_approx_eq!(point.y, 2.0);
+        let parallel = Line::new(Point::new(0.0, 1.0), Vector::new(2.0, 2.0));
+        assert_eq!(a.intersection(&parallel), None);
+    }
+}
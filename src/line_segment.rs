@@ -1,4 +1,4 @@
-use crate::{Point, Ray, Vector};
+use crate::{Point, Ray, Rect, Vector};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -40,4 +40,8 @@ impl<T: en::Num> LineSegment<T> {
     pub fn points(&self) -> impl Iterator<Item = Point<T>> + Clone + DoubleEndedIterator {
         std::iter::once(self.from).chain(std::iter::once(self.to))
     }
+
+    pub fn bounding_rect(&self) -> Rect<T> {
+        Rect::from_points(self.from, self.to)
+    }
 }
@@ -0,0 +1,126 @@
+use crate::{Point, Polygon, PolygonWithHoles, Rect, Transform, Triangulation};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::iter::FromIterator;
+
+/// A collection of disjoint polygons, which is what boolean operations produce.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultiPolygon<T = f32> {
+    pub polygons: Vec<PolygonWithHoles<T>>,
+}
+
+impl<T: en::Num> MultiPolygon<T> {
+    pub fn new(polygons: Vec<PolygonWithHoles<T>>) -> Self {
+        Self { polygons }
+    }
+
+    pub fn empty() -> Self {
+        Self::new(Vec::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.polygons.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.polygons.len()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, PolygonWithHoles<T>> {
+        self.polygons.iter()
+    }
+
+    /// Iterates over every ring of every polygon.
+    pub fn rings(&self) -> impl Iterator<Item = &Polygon<T>> + Clone {
+        self.polygons.iter().flat_map(PolygonWithHoles::rings)
+    }
+
+    /// Returns `None` if there aren't any polygons.
+    pub fn bounding_rect(&self) -> Option<Rect<T>> {
+        self.polygons
+            .iter()
+            .map(PolygonWithHoles::bounding_rect)
+            .reduce(|a, b| a.union(&b))
+    }
+
+    pub fn transform(self, transform: Transform<T>) -> Self {
+        self.map(move |point| point.transform(transform))
+    }
+
+    pub fn map<U: en::Num>(self, mut f: impl FnMut(Point<T>) -> Point<U>) -> MultiPolygon<U> {
+        self.polygons
+            .into_iter()
+            .map(|polygon| polygon.map(&mut f))
+            .collect()
+    }
+
+    pub fn cast<U: en::Num>(self) -> MultiPolygon<U> {
+        self.map(Point::cast)
+    }
+
+    impl_casts!(MultiPolygon);
+}
+
+impl<T: en::Float> MultiPolygon<T> {
+    pub fn area(&self) -> T {
+        self.polygons
+            .iter()
+            .fold(T::zero(), |area, polygon| area + polygon.area())
+    }
+
+    pub fn contains(&self, point: Point<T>) -> bool {
+        self.polygons.iter().any(|polygon| polygon.contains(point))
+    }
+
+    /// Triangulates each polygon and merges the results into a single mesh.
+    pub fn triangulate(&self) -> Triangulation<T> {
+        self.polygons
+            .iter()
+            .fold(Triangulation::default(), |mut merged, polygon| {
+                let Triangulation { points, indices } = polygon.triangulate();
+                let offset = merged.points.len() as u32;
+                merged.points.extend(points);
+                merged
+                    .indices
+                    .extend(indices.into_iter().map(|tri| tri.map(|i| i + offset)));
+                merged
+            })
+    }
+}
+
+impl<T: en::Num> FromIterator<PolygonWithHoles<T>> for MultiPolygon<T> {
+    fn from_iter<I: IntoIterator<Item = PolygonWithHoles<T>>>(polygons: I) -> Self {
+        Self::new(polygons.into_iter().collect())
+    }
+}
+
+impl<T: en::Num> IntoIterator for MultiPolygon<T> {
+    type Item = PolygonWithHoles<T>;
+    type IntoIter = std::vec::IntoIter<PolygonWithHoles<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.polygons.into_iter()
+    }
+}
+
+impl<'a, T: en::Num> IntoIterator for &'a MultiPolygon<T> {
+    type Item = &'a PolygonWithHoles<T>;
+    type IntoIter = std::slice::Iter<'a, PolygonWithHoles<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: en::Num> From<PolygonWithHoles<T>> for MultiPolygon<T> {
+    fn from(polygon: PolygonWithHoles<T>) -> Self {
+        Self::new(vec![polygon])
+    }
+}
+
+impl<T: en::Num> From<Polygon<T>> for MultiPolygon<T> {
+    fn from(polygon: Polygon<T>) -> Self {
+        PolygonWithHoles::from(polygon).into()
+    }
+}
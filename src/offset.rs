@@ -0,0 +1,353 @@
+use crate::{boolean::overlay, MultiPolygon, Point, Polygon, PolygonWithHoles, Polyline, Vector};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How the gap on the outside of a corner gets filled in when offsetting.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Join<T = f32> {
+    /// Extends the adjacent edges until they meet. If that point would be more than `limit` times
+    /// the offset distance away from the original corner, this falls back to `Bevel`; this is the
+    /// same ratio as SVG's `stroke-miterlimit`.
+    Miter(T),
+    /// Rounds off the corner with an arc centered on the original corner. The arc is approximated
+    /// with line segments that stray from it by at most 1% of the offset distance.
+    Round,
+    /// Cuts the corner off with a single straight edge.
+    Bevel,
+}
+
+/// How the ends of an offset polyline are closed off.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Cap {
+    /// Ends flush with the endpoint.
+    Butt,
+    /// Extends past the endpoint by the offset distance.
+    Square,
+    /// Ends with a semicircle centered on the endpoint.
+    Round,
+}
+
+/// The maximum distance between a round join and its true arc, relative to the offset distance.
+const ROUND_TOLERANCE: f64 = 0.01;
+
+/// Builds a ring that's been pushed outwards by `distance`, where "outwards" is the side that has
+/// negative cross products with the ring's edges (i.e. the outside of a clockwise ring).
+///
+/// Along the inside of a corner, the offset edges overlap each other. Rather than trimming them,
+/// they're connected through the original corner, which forms a small loop wound the opposite way;
+/// once everything is resolved with a positive fill rule, those loops cancel out.
+struct Offsetter<T> {
+    distance: T,
+    join: Join<T>,
+    points: Vec<Point<T>>,
+}
+
+impl<T: en::Float> Offsetter<T> {
+    fn new(distance: T, join: Join<T>) -> Self {
+        Self {
+            distance,
+            join,
+            points: Vec::new(),
+        }
+    }
+
+    /// Returns the unit vector pointing outwards from `edge`.
+    fn normal(edge: Vector<T>) -> Vector<T> {
+        Vector::new(edge.dy, -edge.dx).normalize()
+    }
+
+    fn corner(&mut self, corner: Point<T>, incoming: Vector<T>, outgoing: Vector<T>) {
+        let from = Self::normal(incoming) * self.distance;
+        let to = Self::normal(outgoing) * self.distance;
+        let cross = incoming.cross_product(outgoing);
+        if cross == T::zero() && incoming.dot_product(outgoing) > T::zero() {
+            self.points.push(corner + from);
+            return;
+        }
+        if cross * self.distance < T::zero() {
+            self.points.extend([corner + from, corner, corner + to]);
+            return;
+        }
+        match self.join {
+            Join::Miter(limit) => {
+                // The miter point is `1 / cos(θ / 2)` times the distance away from the corner,
+                // where `θ` is the angle between the two offset directions.
+                let cos = from.dot_product(to) / (self.distance * self.distance);
+                if cos > -T::one() && T::two() <= limit * limit * (T::one() + cos) {
+                    self.points.push(corner + (from + to) / (T::one() + cos));
+                } else {
+                    self.points.extend([corner + from, corner + to]);
+                }
+            }
+            Join::Round => {
+                let sweep = cross.abs().atan2(incoming.dot_product(outgoing));
+                // The arc bulges out in the direction we were heading.
+                let sweep = if from.cross_product(incoming) < T::zero() {
+                    -sweep
+                } else {
+                    sweep
+                };
+                self.arc(corner, from, sweep);
+            }
+            Join::Bevel => self.points.extend([corner + from, corner + to]),
+        }
+    }
+
+    /// Closes off the end of a polyline that's heading in `direction`.
+    fn cap(&mut self, end: Point<T>, direction: Vector<T>, cap: Cap) {
+        let side = Self::normal(direction) * self.distance;
+        match cap {
+            Cap::Butt => self.points.extend([end + side, end - side]),
+            Cap::Square => {
+                let ahead = direction.normalize() * self.distance;
+                self.points.extend([end + side + ahead, end - side + ahead]);
+            }
+            Cap::Round => self.arc(end, side, T::PI()),
+        }
+    }
+
+    /// Adds the points along an arc starting at `center + from`, rotating by `sweep` radians
+    /// (where positive is clockwise on screen).
+    fn arc(&mut self, center: Point<T>, from: Vector<T>, sweep: T) {
+        let tolerance: T = en::cast(ROUND_TOLERANCE);
+        let max_step = T::two() * (T::one() - tolerance).acos();
+        let steps = en::cast::<usize, _>((sweep.abs() / max_step).ceil()).max(1);
+        let (sin, cos) = (sweep / en::cast(steps)).sin_cos();
+        let mut offset = from;
+        self.points.push(center + offset);
+        for _ in 0..steps {
+            offset = Vector::new(
+                offset.dx * cos - offset.dy * sin,
+                offset.dx * sin + offset.dy * cos,
+            );
+            self.points.push(center + offset);
+        }
+    }
+
+    /// Offsets a closed ring, wound so that its interior has a positive winding number.
+    fn ring(mut self, points: &[Point<T>]) -> Vec<Point<T>> {
+        let points = distinct_points(points, true);
+        let len = points.len();
+        if len > 1 {
+            for (index, corner) in points.iter().enumerate() {
+                let prev = points[(index + len - 1) % len];
+                let next = points[(index + 1) % len];
+                self.corner(*corner, *corner - prev, next - *corner);
+            }
+        }
+        self.points
+    }
+
+    /// Offsets both sides of an open polyline, going out along one side and back along the other.
+    fn polyline(mut self, points: &[Point<T>], cap: Cap) -> Vec<Point<T>> {
+        let points = distinct_points(points, false);
+        match points.as_slice() {
+            [] => (),
+            [point] => {
+                self.cap(*point, Vector::from_dx(T::one()), cap);
+                self.cap(*point, Vector::from_dx(-T::one()), cap);
+            }
+            [first, .., last] => {
+                let last_index = points.len() - 1;
+                let there_and_back = points.iter().chain(points[1..last_index].iter().rev());
+                for (index, corner) in there_and_back.enumerate() {
+                    if index == 0 {
+                        self.cap(*first, *first - points[1], cap);
+                    } else if index == last_index {
+                        self.cap(*last, *last - points[last_index - 1], cap);
+                    } else {
+                        let (prev, next) = if index < last_index {
+                            (points[index - 1], points[index + 1])
+                        } else {
+                            let index = 2 * last_index - index;
+                            (points[index + 1], points[index - 1])
+                        };
+                        self.corner(*corner, *corner - prev, next - *corner);
+                    }
+                }
+            }
+        }
+        self.points
+    }
+}
+
+/// Removes consecutive duplicate points, including a duplicate closing point if `closed`.
+fn distinct_points<T: en::Float>(points: &[Point<T>], closed: bool) -> Vec<Point<T>> {
+    let mut points = points.to_vec();
+    points.dedup();
+    if closed && points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    points
+}
+
+/// Offsets every ring and resolves the overlaps.
+fn offset_rings<'a, T: en::Float + 'a>(
+    polygons: impl IntoIterator<Item = &'a PolygonWithHoles<T>>,
+    distance: T,
+    join: Join<T>,
+) -> MultiPolygon<T> {
+    let rings: Vec<_> = polygons
+        .into_iter()
+        .flat_map(|polygon| {
+            polygon
+                .clone()
+                .normalize_winding()
+                .rings()
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .map(|ring| Offsetter::new(distance, join).ring(ring.points()))
+        .collect();
+    let rings: Vec<_> = rings.iter().map(Vec::as_slice).collect();
+    overlay(&rings, |windings| windings.iter().sum::<i32>() > 0)
+}
+
+impl<T: en::Float> Polygon<T> {
+    /// Grows the polygon outwards by `distance`, or shrinks it if `distance` is negative. Shrinking
+    /// can split the polygon into pieces or make it vanish entirely, and growing can fill in
+    /// concavities, so the result is a `MultiPolygon`.
+    pub fn offset(&self, distance: T, join: Join<T>) -> MultiPolygon<T> {
+        offset_rings(&[PolygonWithHoles::from(self.clone())], distance, join)
+    }
+}
+
+impl<T: en::Float> PolygonWithHoles<T> {
+    /// Grows the polygon outwards by `distance`, or shrinks it if `distance` is negative. Holes
+    /// shrink as the polygon grows, and vice versa.
+    pub fn offset(&self, distance: T, join: Join<T>) -> MultiPolygon<T> {
+        offset_rings(std::iter::once(self), distance, join)
+    }
+}
+
+impl<T: en::Float> MultiPolygon<T> {
+    /// Grows each polygon outwards by `distance`, or shrinks them if `distance` is negative.
+    /// Polygons that grow into each other are merged.
+    pub fn offset(&self, distance: T, join: Join<T>) -> MultiPolygon<T> {
+        offset_rings(self, distance, join)
+    }
+}
+
+impl<T: en::Float> Polyline<T> {
+    /// Returns the outline of everything within `distance` of the polyline, as if it were stroked
+    /// with a width of twice the distance.
+    pub fn offset(&self, distance: T, join: Join<T>, cap: Cap) -> MultiPolygon<T> {
+        let ring = Offsetter::new(distance.abs(), join).polyline(self.points(), cap);
+        overlay(&[&ring], |windings| windings[0] > 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, Rect, Size};
+
+    fn square() -> Polygon {
+        Rect::from_top_left(Point::zero(), Size::square(10.0)).into()
+    }
+
+    #[test]
+    fn joins() {
+        let square = square();
+        let mitered = square.offset(1.0, Join::Miter(2.0));
+        assert_eq!(mitered.len(), 1);
+        assert_eq!(mitered.polygons[0].outer.len(), 4);
+        assert_approx_eq!(mitered.area(), 144.0);
+        // A right angle needs a miter limit of at least √2.
+        let beveled = square.offset(1.0, Join::Miter(1.4));
+        assert_eq!(beveled.polygons[0].outer.len(), 8);
+        assert_approx_eq!(beveled.area(), 142.0);
+        assert_eq!(square.offset(1.0, Join::Bevel), beveled);
+        let rounded = square.offset(1.0, Join::Round).area();
+        assert!(rounded > 142.0 && rounded < 140.0 + std::f32::consts::PI);
+        assert_eq!(
+            square.clone().reverse().offset(1.0, Join::Bevel).area(),
+            beveled.area()
+        );
+    }
+
+    #[test]
+    fn shrink() {
+        let square = square();
+        let shrunk = square.offset(-2.0, Join::Round);
+        assert_approx_eq!(shrunk.area(), 36.0);
+        assert!(square.offset(-5.0, Join::Miter(4.0)).is_empty());
+
+        // Shrinking a dumbbell pinches it off in the middle.
+        let dumbbell = Polygon::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 1.0),
+            Point::new(6.0, 1.0),
+            Point::new(6.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 4.0),
+            Point::new(6.0, 4.0),
+            Point::new(6.0, 3.0),
+            Point::new(4.0, 3.0),
+            Point::new(4.0, 4.0),
+            Point::new(0.0, 4.0),
+        ]);
+        let pinched = dumbbell.offset(-1.5, Join::Miter(2.0));
+        assert_eq!(pinched.len(), 2);
+        assert_approx_eq!(pinched.area(), 2.0);
+    }
+
+    #[test]
+    fn holes() {
+        let frame = PolygonWithHoles::from(square())
+            .with_hole(Rect::from_top_left(Point::new(3.0, 3.0), Size::square(4.0)).into());
+        let grown = frame.offset(1.0, Join::Miter(2.0));
+        assert_eq!(grown.polygons[0].holes.len(), 1);
+        assert_approx_eq!(grown.area(), 144.0 - 4.0);
+        let filled = frame.offset(2.0, Join::Miter(2.0));
+        assert!(filled.polygons[0].holes.is_empty());
+        assert_approx_eq!(filled.area(), 196.0);
+
+        // Growing two nearby squares merges them.
+        let pair = MultiPolygon::new(vec![
+            square().into(),
+            (square() + Vector::from_dx(11.0)).into(),
+        ]);
+        assert_eq!(pair.offset(1.0, Join::Miter(2.0)).len(), 1);
+    }
+
+    #[test]
+    fn polyline() {
+        let line = Polyline::new(vec![Point::new(0.0, 0.0), Point::new(10.0, 0.0)]);
+        assert_approx_eq!(line.offset(1.0, Join::Bevel, Cap::Butt).area(), 20.0);
+        assert_approx_eq!(line.offset(1.0, Join::Bevel, Cap::Square).area(), 24.0);
+        let round = line.offset(1.0, Join::Bevel, Cap::Round).area();
+        assert!(round > 23.0 && round < 20.0 + std::f32::consts::PI);
+
+        let elbow = Polyline::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+        ]);
+        let stroked = elbow.offset(1.0, Join::Miter(2.0), Cap::Butt);
+        assert_eq!(stroked.len(), 1);
+        assert!(stroked.polygons[0].holes.is_empty());
+        assert_approx_eq!(stroked.area(), 40.0);
+
+        // Strokes that cross themselves are still solid where they overlap.
+        let crossed = Polyline::new(vec![
+            Point::new(0.0, 5.0),
+            Point::new(10.0, 5.0),
+            Point::new(10.0, 10.0),
+            Point::new(5.0, 10.0),
+            Point::new(5.0, 0.0),
+        ]);
+        let stroked = crossed.offset(0.5, Join::Miter(2.0), Cap::Butt);
+        assert_eq!(stroked.len(), 1);
+        assert_eq!(stroked.polygons[0].holes.len(), 1);
+        assert!(stroked.contains(Point::new(5.0, 5.0)));
+        assert!(!stroked.contains(Point::new(7.5, 7.5)));
+
+        let dot = Polyline::new(vec![Point::new(1.0, 1.0)]);
+        assert!(dot.offset(1.0, Join::Round, Cap::Butt).is_empty());
+        assert_approx_eq!(dot.offset(1.0, Join::Round, Cap::Square).area(), 4.0);
+    }
+}
@@ -0,0 +1,501 @@
+use crate::{
+    bezier::Curve, Angle, CubicBezier, Ellipse, EllipseArc, LineSegment, Point, Polygon, Polyline,
+    QuadBezier, Rect, Transform, Vector,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One piece of a `Path`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PathSegment<T = f32> {
+    Line(LineSegment<T>),
+    Quad(QuadBezier<T>),
+    Cubic(CubicBezier<T>),
+    Arc(EllipseArc<T>),
+}
+
+impl<T: en::Float> PathSegment<T> {
+    pub fn from(&self) -> Point<T> {
+        match self {
+            Self::Line(line) => line.from,
+            Self::Quad(quad) => quad.from,
+            Self::Cubic(cubic) => cubic.from,
+            Self::Arc(arc) => arc.from(),
+        }
+    }
+
+    pub fn to(&self) -> Point<T> {
+        match self {
+            Self::Line(line) => line.to,
+            Self::Quad(quad) => quad.to,
+            Self::Cubic(cubic) => cubic.to,
+            Self::Arc(arc) => arc.to(),
+        }
+    }
+
+    /// Returns the point `t` of the way along the segment, where `t` is in `0..=1`.
+    pub fn point_at(&self, t: T) -> Point<T> {
+        match self {
+            Self::Line(line) => line.from + line.vector() * t,
+            Self::Quad(quad) => quad.point_at(t),
+            Self::Cubic(cubic) => cubic.point_at(t),
+            Self::Arc(arc) => arc.point_at(t),
+        }
+    }
+
+    /// Returns the unit vector pointing along the segment.
+    pub fn tangent(&self, t: T) -> Vector<T> {
+        match self {
+            Self::Line(line) => line.vector().normalize(),
+            Self::Quad(quad) => quad.tangent(t),
+            Self::Cubic(cubic) => cubic.tangent(t),
+            Self::Arc(arc) => arc.tangent(t),
+        }
+    }
+
+    pub fn bounding_rect(&self) -> Rect<T> {
+        match self {
+            Self::Line(line) => line.bounding_rect(),
+            Self::Quad(quad) => quad.bounding_rect(),
+            Self::Cubic(cubic) => cubic.bounding_rect(),
+            Self::Arc(arc) => arc.bounding_rect(),
+        }
+    }
+
+    pub fn length(&self) -> T {
+        match self {
+            Self::Line(line) => line.length(),
+            Self::Quad(quad) => quad.length(),
+            Self::Cubic(cubic) => cubic.length(),
+            Self::Arc(arc) => arc.length(),
+        }
+    }
+
+    /// Returns the `t` where the length of the segment up to that point is `distance`.
+    pub fn t_at_length(&self, distance: T) -> T {
+        match self {
+            Self::Line(line) => {
+                let length = line.length();
+                if distance <= T::zero() || length == T::zero() {
+                    T::zero()
+                } else if distance >= length {
+                    T::one()
+                } else {
+                    distance / length
+                }
+            }
+            Self::Quad(quad) => quad.t_at_length(distance),
+            Self::Cubic(cubic) => cubic.t_at_length(distance),
+            Self::Arc(arc) => arc.t_at_length(distance),
+        }
+    }
+
+    /// Approximates the segment with a polyline that strays from it by no more than `tolerance`,
+    /// which should be positive. Lines are already flat, so they only have their two endpoints.
+    pub fn flatten(&self, tolerance: T) -> Polyline<T> {
+        match self {
+            Self::Line(line) => Polyline::new(vec![line.from, line.to]),
+            Self::Quad(quad) => quad.flatten(tolerance),
+            Self::Cubic(cubic) => cubic.flatten(tolerance),
+            Self::Arc(arc) => arc.flatten(tolerance),
+        }
+    }
+
+    /// Returns the same segment with its direction reversed.
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Line(line) => Self::Line(LineSegment::new(line.to, line.from)),
+            Self::Quad(quad) => Self::Quad(quad.reverse()),
+            Self::Cubic(cubic) => Self::Cubic(cubic.reverse()),
+            Self::Arc(arc) => Self::Arc(arc.reverse()),
+        }
+    }
+}
+
+/// A connected run of segments within a `Path`, which starts wherever `Path::move_to` left off.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Subpath<T = f32> {
+    start: Point<T>,
+    segments: Vec<PathSegment<T>>,
+    closed: bool,
+}
+
+impl<T: en::Float> Subpath<T> {
+    pub fn start(&self) -> Point<T> {
+        self.start
+    }
+
+    /// Returns where the subpath ends up, which is back at the start if it's closed.
+    pub fn end(&self) -> Point<T> {
+        match self.segments.last() {
+            Some(segment) if !self.closed => segment.to(),
+            _ => self.start,
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Iterates over the subpath's segments. Closed subpaths end with a line back to the start,
+    /// unless they're already there.
+    pub fn segments(&self) -> impl Iterator<Item = PathSegment<T>> + Clone + '_ {
+        let closing = self
+            .segments
+            .last()
+            .filter(|last| self.closed && last.to() != self.start)
+            .map(|last| PathSegment::Line(LineSegment::new(last.to(), self.start)));
+        self.segments.iter().copied().chain(closing)
+    }
+
+    pub fn bounding_rect(&self) -> Rect<T> {
+        self.segments.iter().fold(
+            Rect::from_points(self.start, self.start),
+            |rect, segment| rect.union(&segment.bounding_rect()),
+        )
+    }
+
+    pub fn length(&self) -> T {
+        self.segments()
+            .fold(T::zero(), |sum, segment| sum + segment.length())
+    }
+
+    /// Approximates the subpath with a polyline that strays from it by no more than `tolerance`,
+    /// which should be positive. Closed subpaths end back at their start.
+    pub fn flatten(&self, tolerance: T) -> Polyline<T> {
+        let mut points = vec![self.start];
+        for segment in self.segments() {
+            // Each segment starts where the last one ended.
+            points.extend(segment.flatten(tolerance).into_points().into_iter().skip(1));
+        }
+        Polyline::new(points)
+    }
+
+    /// Like `flatten`, but closes the subpath even if it isn't already, the same way that filling
+    /// it would.
+    pub fn flatten_closed(&self, tolerance: T) -> Polygon<T> {
+        let mut points = self.flatten(tolerance).into_points();
+        if points.len() > 1 && points.last() == points.first() {
+            points.pop();
+        }
+        Polygon::new(points)
+    }
+
+    /// Transforms every segment. Arcs are turned into cubic Béziers if `transform` rotates or skews
+    /// them, since `Ellipse` is always aligned with the axes.
+    pub fn transform(self, transform: Transform<T>) -> Self {
+        let mut segments = Vec::with_capacity(self.segments.len());
+        for segment in self.segments {
+            match segment {
+                PathSegment::Line(line) => segments.push(PathSegment::Line(
+                    line.map(|point| point.transform(transform)),
+                )),
+                PathSegment::Quad(quad) => {
+                    segments.push(PathSegment::Quad(quad.transform(transform)))
+                }
+                PathSegment::Cubic(cubic) => {
+                    segments.push(PathSegment::Cubic(cubic.transform(transform)))
+                }
+                PathSegment::Arc(arc) => match arc.transform(transform) {
+                    Some(arc) => segments.push(PathSegment::Arc(arc)),
+                    None => segments.extend(
+                        arc.to_cubic_beziers()
+                            .into_iter()
+                            .map(|cubic| PathSegment::Cubic(cubic.transform(transform))),
+                    ),
+                },
+            }
+        }
+        Self {
+            start: self.start.transform(transform),
+            segments,
+            closed: self.closed,
+        }
+    }
+}
+
+/// A shape made out of lines, curves, and arcs, like a canvas or SVG path. Paths are built up by
+/// chaining calls starting from `Path::new`, e.g. `Path::new().move_to(a).line_to(b).close()`.
+///
+/// Drawing without a current point (i.e. before any `move_to`) starts a new subpath at the first
+/// point of whatever's being drawn, which for lines means that they only move there.
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Path<T = f32> {
+    subpaths: Vec<Subpath<T>>,
+}
+
+impl<T: en::Float> Path<T> {
+    pub fn new() -> Self {
+        Self {
+            subpaths: Vec::new(),
+        }
+    }
+
+    pub fn subpaths(&self) -> &[Subpath<T>] {
+        &self.subpaths
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.subpaths.is_empty()
+    }
+
+    /// Returns where the next segment will start, if there's anywhere to start from.
+    pub fn current_point(&self) -> Option<Point<T>> {
+        self.subpaths.last().map(Subpath::end)
+    }
+
+    /// Starts a new subpath at `point`.
+    pub fn move_to(mut self, point: Point<T>) -> Self {
+        match self.subpaths.last_mut() {
+            // Moving again without drawing anything just moves somewhere else.
+            Some(last) if last.segments.is_empty() && !last.closed => last.start = point,
+            _ => self.subpaths.push(Subpath {
+                start: point,
+                segments: Vec::new(),
+                closed: false,
+            }),
+        }
+        self
+    }
+
+    /// Appends `segment`, which should start at the current point.
+    fn push(mut self, segment: PathSegment<T>) -> Self {
+        let start = match self.current_point() {
+            Some(point) => point,
+            None => segment.from(),
+        };
+        // Anything drawn after closing a subpath starts a new one where the closed one started.
+        if !matches!(self.subpaths.last(), Some(last) if !last.closed) {
+            self = self.move_to(start);
+        }
+        self.subpaths.last_mut().unwrap().segments.push(segment);
+        self
+    }
+
+    pub fn line_to(self, point: Point<T>) -> Self {
+        match self.current_point() {
+            Some(from) => self.push(PathSegment::Line(LineSegment::new(from, point))),
+            None => self.move_to(point),
+        }
+    }
+
+    /// Draws a quadratic Bézier curve to `to`, heading towards `ctrl`.
+    pub fn quad_to(self, ctrl: Point<T>, to: Point<T>) -> Self {
+        let from = self.current_point().unwrap_or(ctrl);
+        self.push(PathSegment::Quad(QuadBezier::new(from, ctrl, to)))
+    }
+
+    /// Draws a cubic Bézier curve to `to`, heading towards `ctrl1` and arriving from `ctrl2`.
+    pub fn cubic_to(self, ctrl1: Point<T>, ctrl2: Point<T>, to: Point<T>) -> Self {
+        let from = self.current_point().unwrap_or(ctrl1);
+        self.push(PathSegment::Cubic(CubicBezier::new(from, ctrl1, ctrl2, to)))
+    }
+
+    /// Draws the part of `ellipse` from `start_angle` to `end_angle`, following the same
+    /// conventions as `Ellipse::arc_points`. If the arc doesn't start at the current point, a line
+    /// is drawn to its start first, ignoring the rounding error in finding where it starts.
+    pub fn arc_to(self, ellipse: Ellipse<T>, start_angle: Angle<T>, end_angle: Angle<T>) -> Self {
+        let arc = EllipseArc::new(ellipse, start_angle, end_angle);
+        let path = match self.current_point() {
+            Some(point) if (point - arc.from()).magnitude() > arc.tolerance() => {
+                self.line_to(arc.from())
+            }
+            _ => self,
+        };
+        path.push(PathSegment::Arc(arc))
+    }
+
+    /// Closes the current subpath with a line back to its start.
+    pub fn close(mut self) -> Self {
+        if let Some(last) = self.subpaths.last_mut() {
+            last.closed = true;
+        }
+        self
+    }
+
+    /// Iterates over the segments of every subpath, including the lines that close them.
+    pub fn segments(&self) -> impl Iterator<Item = PathSegment<T>> + '_ {
+        self.subpaths.iter().flat_map(Subpath::segments)
+    }
+
+    pub fn bounding_rect(&self) -> Rect<T> {
+        let mut rects = self.subpaths.iter().map(Subpath::bounding_rect);
+        let first = rects.next().unwrap_or_else(Rect::zero);
+        rects.fold(first, |union, rect| union.union(&rect))
+    }
+
+    pub fn transform(self, transform: Transform<T>) -> Self {
+        Self {
+            subpaths: self
+                .subpaths
+                .into_iter()
+                .map(|subpath| subpath.transform(transform))
+                .collect(),
+        }
+    }
+
+    /// Approximates each subpath with a polyline that strays from it by no more than `tolerance`,
+    /// which should be positive.
+    pub fn flatten(&self, tolerance: T) -> Vec<Polyline<T>> {
+        self.subpaths
+            .iter()
+            .map(|subpath| subpath.flatten(tolerance))
+            .collect()
+    }
+
+    /// Approximates each subpath with a polygon, closing them the same way that filling the path
+    /// would.
+    pub fn flatten_closed(&self, tolerance: T) -> Vec<Polygon<T>> {
+        self.subpaths
+            .iter()
+            .map(|subpath| subpath.flatten_closed(tolerance))
+            .collect()
+    }
+
+    /// Returns the total length of every subpath. Moving between subpaths doesn't count.
+    pub fn length(&self) -> T {
+        self.subpaths
+            .iter()
+            .fold(T::zero(), |sum, subpath| sum + subpath.length())
+    }
+
+    /// Finds the segment that's `distance` along the path, along with how far along it that is.
+    fn segment_at_length(&self, distance: T) -> Option<(PathSegment<T>, T)> {
+        let mut remaining = distance;
+        let mut segments = self.segments().peekable();
+        while let Some(segment) = segments.next() {
+            let length = segment.length();
+            if remaining <= length || segments.peek().is_none() {
+                return Some((segment, segment.t_at_length(remaining)));
+            }
+            remaining = remaining - length;
+        }
+        None
+    }
+
+    /// Returns the point that's `distance` along the path, clamped to its ends. This is `None` if
+    /// the path doesn't have any segments.
+    pub fn point_at_length(&self, distance: T) -> Option<Point<T>> {
+        self.segment_at_length(distance)
+            .map(|(segment, t)| segment.point_at(t))
+    }
+
+    /// Returns the unit vector pointing along the path at `distance` along it, clamped to its ends.
+    /// This is `None` if the path doesn't have any segments.
+    pub fn tangent_at_length(&self, distance: T) -> Option<Vector<T>> {
+        self.segment_at_length(distance)
+            .map(|(segment, t)| segment.tangent(t))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, assert_points_eq, Size};
+    use std::f32::consts::PI;
+
+    /// A 4x4 square with the top right corner rounded off.
+    fn rounded() -> Path {
+        Path::new()
+            .move_to(Point::new(0.0, 0.0))
+            .line_to(Point::new(2.0, 0.0))
+            .arc_to(
+                Ellipse::new(Point::new(2.0, 2.0), Size::square(2.0)),
+                Angle::FRAC_PI_2(),
+                Angle::ZERO(),
+            )
+            .line_to(Point::new(4.0, 4.0))
+            .line_to(Point::new(0.0, 4.0))
+            .close()
+    }
+
+    #[test]
+    fn build() {
+        let path = rounded();
+        assert_eq!(path.subpaths().len(), 1);
+        assert_eq!(path.segments().count(), 5);
+        assert_eq!(path.current_point(), Some(Point::new(0.0, 0.0)));
+        let path = path
+            .line_to(Point::new(-1.0, -1.0))
+            .move_to(Point::new(9.0, 9.0))
+            .quad_to(Point::new(10.0, 10.0), Point::new(11.0, 9.0));
+        // Drawing after closing starts over from where the closed subpath started.
+        assert_eq!(path.subpaths()[1].start(), Point::new(0.0, 0.0));
+        assert_eq!(path.subpaths().len(), 3);
+        assert_eq!(path.current_point(), Some(Point::new(11.0, 9.0)));
+        assert_eq!(
+            Path::new().line_to(Point::new(1.0, 1.0)).current_point(),
+            Some(Point::new(1.0, 1.0))
+        );
+        assert_eq!(Path::<f32>::new().segments().count(), 0);
+    }
+
+    #[test]
+    fn measure() {
+        let path = rounded();
+        assert_approx_eq!(path.length(), 12.0 + PI);
+        assert_eq!(
+            path.bounding_rect(),
+            Rect::from_top_left(Point::zero(), Size::square(4.0))
+        );
+        assert_points_eq!(path.point_at_length(1.0).unwrap(), Point::new(1.0, 0.0));
+        // Halfway around the arc.
+        let corner =
+            Point::new(2.0, 2.0) + Vector::new(1.0, -1.0) * std::f32::consts::FRAC_1_SQRT_2 * 2.0;
+        assert_points_eq!(path.point_at_length(2.0 + PI / 2.0).unwrap(), corner);
+        let tangent = path.tangent_at_length(2.0 + PI / 2.0).unwrap();
+        assert_approx_eq!(tangent.dx, tangent.dy);
+        assert_points_eq!(path.point_at_length(100.0).unwrap(), Point::new(0.0, 0.0));
+        assert_points_eq!(
+            path.tangent_at_length(-1.0).unwrap().to_point(),
+            Point::new(1.0, 0.0),
+        );
+        assert_eq!(Path::<f32>::new().point_at_length(1.0), None);
+    }
+
+    #[test]
+    fn transform() {
+        let path = rounded();
+        let flip = Transform::from_scale(-1.0, 2.0);
+        let rotate = Transform::from_rotation(Angle::FRAC_PI_4(), Point::new(2.0, 2.0));
+        // Flipping keeps the arc, but rotating it turns it into curves.
+        let flipped: Vec<_> = path.clone().transform(flip).segments().collect();
+        assert!(matches!(flipped[1], PathSegment::Arc(_)));
+        let rotated: Vec<_> = path.clone().transform(rotate).segments().collect();
+        assert!(matches!(rotated[1], PathSegment::Cubic(_)));
+        for (transform, transformed) in [(flip, flipped), (rotate, rotated)] {
+            let original: Vec<_> = path.segments().collect();
+            assert_eq!(original.len(), transformed.len());
+            for (segment, transformed) in original.iter().zip(&transformed) {
+                for t in [0.0, 0.5, 1.0] {
+                    let expected = segment.point_at(t).transform(transform);
+                    let actual = transformed.point_at(t);
+                    assert!((expected - actual).magnitude() < 0.001);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn flatten() {
+        let path = rounded()
+            .move_to(Point::new(10.0, 0.0))
+            .line_to(Point::new(11.0, 0.0))
+            .line_to(Point::new(11.0, 1.0));
+        let polylines = path.flatten(0.01);
+        assert_eq!(polylines.len(), 2);
+        let points = polylines[0].points();
+        // Closed subpaths come back around to the start.
+        assert_eq!(points.first(), Some(&Point::zero()));
+        assert_eq!(points.last(), Some(&Point::zero()));
+        assert!(points.len() > 6);
+        assert!(points.contains(&Point::new(4.0, 4.0)));
+        assert_eq!(polylines[1].len(), 3);
+        let polygons = path.flatten_closed(0.01);
+        assert_eq!(polygons[0].len(), points.len() - 1);
+        assert_eq!(polygons[1].len(), 3);
+    }
+}
@@ -0,0 +1,344 @@
+use crate::{orient2d, LineSegment, Point, Quad, Rect, Transform, Vector};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    iter::FromIterator,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+/// Decides which regions of an outline count as "inside" when the outline overlaps itself.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FillRule {
+    /// A point is inside if the outline winds around it an odd number of times.
+    EvenOdd,
+    /// A point is inside if the outline winds around it any nonzero number of times.
+    NonZero,
+}
+
+impl FillRule {
+    pub fn is_inside(self, winding_number: i32) -> bool {
+        match self {
+            Self::EvenOdd => winding_number % 2 != 0,
+            Self::NonZero => winding_number != 0,
+        }
+    }
+}
+
+/// The order in which a polygon's points are visited, as seen on screen (i.e. with y pointing down).
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// A closed polygon. The last point is implicitly connected back to the first, so it shouldn't be
+/// repeated.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Polygon<T = f32> {
+    points: Vec<Point<T>>,
+}
+
+impl<T: en::Num> Polygon<T> {
+    pub fn new(points: Vec<Point<T>>) -> Self {
+        Self { points }
+    }
+
+    pub fn points(&self) -> &[Point<T>] {
+        &self.points
+    }
+
+    pub fn into_points(self) -> Vec<Point<T>> {
+        self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Iterates over the polygon's edges, including the closing edge from the last point back to
+    /// the first.
+    pub fn line_segments(&self) -> impl Iterator<Item = LineSegment<T>> + Clone + '_ {
+        let next = self.points.iter().cycle().skip(1);
+        self.points
+            .iter()
+            .zip(next)
+            .map(|(from, to)| LineSegment::new(*from, *to))
+    }
+
+    pub fn bounding_rect(&self) -> Rect<T> {
+        Rect::from_iter(&self.points)
+    }
+
+    /// Returns the same polygon with its points visited in the opposite order.
+    pub fn reverse(mut self) -> Self {
+        self.points.reverse();
+        self
+    }
+
+    pub fn transform(self, transform: Transform<T>) -> Self {
+        self.map(move |point| point.transform(transform))
+    }
+
+    pub fn map<U: en::Num>(self, f: impl FnMut(Point<T>) -> Point<U>) -> Polygon<U> {
+        Polygon::new(self.points.into_iter().map(f).collect())
+    }
+
+    pub fn cast<U: en::Num>(self) -> Polygon<U> {
+        self.map(Point::cast)
+    }
+
+    impl_casts!(Polygon);
+}
+
+impl<T: en::Float> Polygon<T> {
+    /// Returns the area enclosed by the polygon, which is positive if the polygon is wound
+    /// clockwise and negative if it's wound counter-clockwise.
+    pub fn signed_area(&self) -> T {
+        self.line_segments()
+            .fold(T::zero(), |sum, edge| {
+                sum + edge.from.to_vector().cross_product(edge.to.to_vector())
+            })
+            .halved()
+    }
+
+    pub fn area(&self) -> T {
+        self.signed_area().abs()
+    }
+
+    /// Returns `None` if the polygon has no area.
+    pub fn winding(&self) -> Option<Winding> {
+        let signed_area = self.signed_area();
+        if signed_area > T::zero() {
+            Some(Winding::Clockwise)
+        } else if signed_area < T::zero() {
+            Some(Winding::CounterClockwise)
+        } else {
+            None
+        }
+    }
+
+    /// Reverses the polygon if needed so that it has the requested winding. Polygons without area
+    /// are returned unchanged.
+    pub fn with_winding(self, winding: Winding) -> Self {
+        match self.winding() {
+            Some(current) if current != winding => self.reverse(),
+            _ => self,
+        }
+    }
+
+    /// Returns the center of mass of the polygon. If the polygon has no area, this falls back to
+    /// the average of its points.
+    pub fn centroid(&self) -> Point<T> {
+        let signed_area = self.signed_area();
+        if signed_area == T::zero() {
+            let sum = self
+                .points
+                .iter()
+                .fold(Vector::zero(), |sum, point| sum + point.to_vector());
+            return (sum / en::cast::<T, _>(self.points.len().max(1))).to_point();
+        }
+        let sum = self.line_segments().fold(Vector::zero(), |sum, edge| {
+            let cross = edge.from.to_vector().cross_product(edge.to.to_vector());
+            sum + (edge.from.to_vector() + edge.to.to_vector()) * cross
+        });
+        (sum / (signed_area * en::cast(6))).to_point()
+    }
+
+    pub fn perimeter(&self) -> T {
+        self.line_segments()
+            .fold(T::zero(), |sum, edge| sum + edge.length())
+    }
+
+    /// Returns `true` if every interior angle is at most 180º and the outline doesn't loop around
+    /// more than once. Collinear points are allowed.
+    pub fn is_convex(&self) -> bool {
+        if self.points.len() < 3 {
+            return false;
+        }
+        let mut sign = T::zero();
+        let mut turning = T::zero();
+        let edges = self.line_segments().map(|edge| edge.vector());
+        for (a, b) in edges.clone().zip(edges.cycle().skip(1)) {
+            let cross = a.cross_product(b);
+            if cross * sign < T::zero() {
+                return false;
+            }
+            if cross != T::zero() {
+                sign = cross;
+            }
+            turning = turning + cross.atan2(a.dot_product(b));
+        }
+        // A convex polygon turns exactly once, while a star-shaped outline turns at least twice.
+        sign != T::zero() && turning.abs() < T::PI() * T::three()
+    }
+
+    /// Returns how many times the polygon winds around `point`. This is positive for clockwise
+    /// windings and negative for counter-clockwise windings.
+    pub fn winding_number(&self, point: Point<T>) -> i32 {
+        self.line_segments()
+            .map(|edge| winding_contribution(edge, point))
+            .sum()
+    }
+
+    pub fn contains(&self, point: Point<T>, fill_rule: FillRule) -> bool {
+        fill_rule.is_inside(self.winding_number(point))
+    }
+}
+
+/// Returns how much `edge` adds to the winding number around `point`, counting crossings of a ray
+/// cast from `point` in the +x direction.
+pub(crate) fn winding_contribution<T: en::Float>(edge: LineSegment<T>, point: Point<T>) -> i32 {
+    let side = orient2d(edge.from, edge.to, point);
+    if edge.from.y <= point.y {
+        if edge.to.y > point.y && side > 0.0 {
+            return 1;
+        }
+    } else if edge.to.y <= point.y && side < 0.0 {
+        return -1;
+    }
+    0
+}
+
+impl<T: en::Num> FromIterator<Point<T>> for Polygon<T> {
+    fn from_iter<I: IntoIterator<Item = Point<T>>>(points: I) -> Self {
+        Self::new(points.into_iter().collect())
+    }
+}
+
+impl<T: en::Num> From<Vec<Point<T>>> for Polygon<T> {
+    fn from(points: Vec<Point<T>>) -> Self {
+        Self::new(points)
+    }
+}
+
+impl<T: en::Num> From<Rect<T>> for Polygon<T> {
+    fn from(rect: Rect<T>) -> Self {
+        rect.clockwise_points().collect()
+    }
+}
+
+impl<T: en::Num> From<Quad<T>> for Polygon<T> {
+    fn from(quad: Quad<T>) -> Self {
+        Self::new(vec![quad.a, quad.b, quad.c, quad.d])
+    }
+}
+
+impl<T: en::Num> Add<Vector<T>> for Polygon<T> {
+    type Output = Self;
+    fn add(self, rhs: Vector<T>) -> Self::Output {
+        self.map(move |point| point + rhs)
+    }
+}
+
+impl<T: en::Num> AddAssign<Vector<T>> for Polygon<T> {
+    fn add_assign(&mut self, rhs: Vector<T>) {
+        for point in &mut self.points {
+            *point += rhs;
+        }
+    }
+}
+
+impl<T: en::Num> Sub<Vector<T>> for Polygon<T> {
+    type Output = Self;
+    fn sub(self, rhs: Vector<T>) -> Self::Output {
+        self.map(move |point| point - rhs)
+    }
+}
+
+impl<T: en::Num> SubAssign<Vector<T>> for Polygon<T> {
+    fn sub_assign(&mut self, rhs: Vector<T>) {
+        for point in &mut self.points {
+            *point -= rhs;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, Size};
+
+    fn star() -> Polygon {
+        // A pentagram, whose center is wound around twice.
+        Polygon::new(vec![
+            Point::new(0.0, -10.0),
+            Point::new(5.878, 8.09),
+            Point::new(-9.511, -3.09),
+            Point::new(9.511, -3.09),
+            Point::new(-5.878, 8.09),
+        ])
+    }
+
+    #[test]
+    fn area_and_winding() {
+        let rect = Polygon::from(Rect::from_top_left(
+            Point::new(1.0, 2.0),
+            Size::new(4.0, 3.0),
+        ));
+        assert_approx_eq!(rect.signed_area(), 12.0);
+        assert_eq!(rect.winding(), Some(Winding::Clockwise));
+        let reversed = rect.clone().reverse();
+        assert_approx_eq!(reversed.signed_area(), -12.0);
+        assert_eq!(reversed.winding(), Some(Winding::CounterClockwise));
+        assert_eq!(reversed.with_winding(Winding::Clockwise), rect);
+        assert_approx_eq!(rect.perimeter(), 14.0);
+    }
+
+    #[test]
+    fn centroid() {
+        let triangle = Polygon::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(6.0, 0.0),
+            Point::new(0.0, 3.0),
+        ]);
+        let centroid = triangle.centroid();
+        assert_approx_eq!(centroid.x, 2.0);
+        assert_approx_eq!(centroid.y, 1.0);
+    }
+
+    #[test]
+    fn convexity() {
+        let square = Polygon::from(Rect::from_top_left(Point::zero(), Size::square(2.0)));
+        assert!(square.is_convex());
+        assert!(square.clone().reverse().is_convex());
+        let dart = Polygon::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(4.0, 0.0),
+            Point::new(2.0, 4.0),
+        ]);
+        assert!(!dart.is_convex());
+        assert!(!star().is_convex());
+    }
+
+    #[test]
+    fn contains() {
+        let star = star();
+        let center = Point::zero();
+        assert_eq!(star.winding_number(center).abs(), 2);
+        assert!(star.contains(center, FillRule::NonZero));
+        assert!(!star.contains(center, FillRule::EvenOdd));
+        let tip = Point::new(0.0, -8.0);
+        assert!(star.contains(tip, FillRule::NonZero));
+        assert!(star.contains(tip, FillRule::EvenOdd));
+        assert!(!star.contains(Point::new(0.0, 9.0), FillRule::NonZero));
+    }
+
+    #[test]
+    fn transform() {
+        let square = Polygon::from(Rect::from_top_left(Point::zero(), Size::square(2.0)));
+        let moved = square.transform(Transform::from_translation(3.0, 4.0));
+        assert_eq!(
+            moved.bounding_rect(),
+            Rect::from_top_left(Point::new(3.0, 4.0), Size::square(2.0))
+        );
+        assert_approx_eq!(moved.area(), 4.0);
+    }
+}
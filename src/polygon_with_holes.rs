@@ -0,0 +1,88 @@
+use crate::{FillRule, Point, Polygon, Rect, Transform, Winding};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A polygon with zero or more holes cut out of it. Holes are expected to lie within `outer` and to
+/// not overlap each other, but their winding doesn't matter.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PolygonWithHoles<T = f32> {
+    pub outer: Polygon<T>,
+    pub holes: Vec<Polygon<T>>,
+}
+
+impl<T: en::Num> PolygonWithHoles<T> {
+    pub fn new(outer: Polygon<T>, holes: Vec<Polygon<T>>) -> Self {
+        Self { outer, holes }
+    }
+
+    pub fn with_hole(mut self, hole: Polygon<T>) -> Self {
+        self.holes.push(hole);
+        self
+    }
+
+    /// Iterates over the outer ring followed by each hole.
+    pub fn rings(&self) -> impl Iterator<Item = &Polygon<T>> + Clone {
+        std::iter::once(&self.outer).chain(&self.holes)
+    }
+
+    pub fn bounding_rect(&self) -> Rect<T> {
+        self.outer.bounding_rect()
+    }
+
+    pub fn transform(self, transform: Transform<T>) -> Self {
+        self.map(move |point| point.transform(transform))
+    }
+
+    pub fn map<U: en::Num>(self, mut f: impl FnMut(Point<T>) -> Point<U>) -> PolygonWithHoles<U> {
+        PolygonWithHoles::new(
+            self.outer.map(&mut f),
+            self.holes
+                .into_iter()
+                .map(|hole| hole.map(&mut f))
+                .collect(),
+        )
+    }
+
+    pub fn cast<U: en::Num>(self) -> PolygonWithHoles<U> {
+        self.map(Point::cast)
+    }
+
+    impl_casts!(PolygonWithHoles);
+}
+
+impl<T: en::Float> PolygonWithHoles<T> {
+    /// Returns the area of the outer ring minus the area of each hole.
+    pub fn area(&self) -> T {
+        self.holes
+            .iter()
+            .fold(self.outer.area(), |area, hole| area - hole.area())
+    }
+
+    /// Returns the same polygon with the outer ring wound clockwise and the holes wound
+    /// counter-clockwise.
+    pub fn normalize_winding(self) -> Self {
+        Self::new(
+            self.outer.with_winding(Winding::Clockwise),
+            self.holes
+                .into_iter()
+                .map(|hole| hole.with_winding(Winding::CounterClockwise))
+                .collect(),
+        )
+    }
+
+    /// Returns `true` if `point` is inside the outer ring but not inside any of the holes.
+    pub fn contains(&self, point: Point<T>) -> bool {
+        self.outer.contains(point, FillRule::NonZero)
+            && !self
+                .holes
+                .iter()
+                .any(|hole| hole.contains(point, FillRule::NonZero))
+    }
+}
+
+impl<T: en::Num> From<Polygon<T>> for PolygonWithHoles<T> {
+    fn from(outer: Polygon<T>) -> Self {
+        Self::new(outer, Vec::new())
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{LineSegment, Point, Rect, Transform};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::iter::FromIterator;
+
+/// An open chain of line segments. Unlike a `Polygon`, the last point isn't connected back to the
+/// first.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Polyline<T = f32> {
+    points: Vec<Point<T>>,
+}
+
+impl<T: en::Num> Polyline<T> {
+    pub fn new(points: Vec<Point<T>>) -> Self {
+        Self { points }
+    }
+
+    pub fn points(&self) -> &[Point<T>] {
+        &self.points
+    }
+
+    pub fn into_points(self) -> Vec<Point<T>> {
+        self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn line_segments(&self) -> impl Iterator<Item = LineSegment<T>> + Clone + '_ {
+        self.points
+            .windows(2)
+            .map(|pair| LineSegment::new(pair[0], pair[1]))
+    }
+
+    pub fn bounding_rect(&self) -> Rect<T> {
+        Rect::from_iter(&self.points)
+    }
+
+    /// Returns the same polyline with its points visited in the opposite order.
+    pub fn reverse(mut self) -> Self {
+        self.points.reverse();
+        self
+    }
+
+    pub fn transform(self, transform: Transform<T>) -> Self {
+        self.map(move |point| point.transform(transform))
+    }
+
+    pub fn map<U: en::Num>(self, f: impl FnMut(Point<T>) -> Point<U>) -> Polyline<U> {
+        Polyline::new(self.points.into_iter().map(f).collect())
+    }
+
+    pub fn cast<U: en::Num>(self) -> Polyline<U> {
+        self.map(Point::cast)
+    }
+
+    impl_casts!(Polyline);
+}
+
+impl<T: en::Float> Polyline<T> {
+    pub fn length(&self) -> T {
+        self.line_segments()
+            .fold(T::zero(), |sum, segment| sum + segment.length())
+    }
+}
+
+impl<T: en::Num> FromIterator<Point<T>> for Polyline<T> {
+    fn from_iter<I: IntoIterator<Item = Point<T>>>(points: I) -> Self {
+        Self::new(points.into_iter().collect())
+    }
+}
+
+impl<T: en::Num> From<Vec<Point<T>>> for Polyline<T> {
+    fn from(points: Vec<Point<T>>) -> Self {
+        Self::new(points)
+    }
+}
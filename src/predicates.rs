@@ -0,0 +1,233 @@
+use crate::Point;
+
+// These error bounds are from Jonathan Shewchuk's "Adaptive Precision Floating-Point Arithmetic
+// and Fast Robust Geometric Predicates".
+const EPSILON: f64 = f64::EPSILON / 2.0;
+const ORIENT_BOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const INCIRCLE_BOUND: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+/// Returns a positive value if `a`, `b`, and `c` wind clockwise (as seen on screen, i.e. with y
+/// pointing down), a negative value if they wind counter-clockwise, and zero if they're collinear.
+/// The magnitude is roughly twice the area of the triangle.
+///
+/// Unlike a plain cross product, the sign is always correct for `f32` and `f64` coordinates. Most
+/// inputs are settled by a quick floating point test, and only nearly collinear points fall back to
+/// exact arithmetic.
+pub fn orient2d<T: en::Float>(a: Point<T>, b: Point<T>, c: Point<T>) -> f64 {
+    let (a, b, c) = (a.to_f64(), b.to_f64(), c.to_f64());
+    let left = (a.x - c.x) * (b.y - c.y);
+    let right = (a.y - c.y) * (b.x - c.x);
+    let det = left - right;
+    // If the two products have different signs, there can't be any cancellation.
+    let sum = if left > 0.0 && right > 0.0 {
+        left + right
+    } else if left < 0.0 && right < 0.0 {
+        -left - right
+    } else {
+        return det;
+    };
+    if det.abs() >= ORIENT_BOUND * sum {
+        return det;
+    }
+    let (adx, ady) = (difference(a.x, c.x), difference(a.y, c.y));
+    let (bdx, bdy) = (difference(b.x, c.x), difference(b.y, c.y));
+    estimate(&cross(&adx, &ady, &bdx, &bdy))
+}
+
+/// Returns a positive value if `d` is inside the circle passing through `a`, `b`, and `c`, a
+/// negative value if it's outside, and zero if it's on the circle. This assumes that `a`, `b`, and
+/// `c` wind clockwise (as seen on screen), and the sign flips if they don't.
+///
+/// Like `orient2d`, the sign is always correct for `f32` and `f64` coordinates.
+pub fn incircle<T: en::Float>(a: Point<T>, b: Point<T>, c: Point<T>, d: Point<T>) -> f64 {
+    let (a, b, c, d) = (a.to_f64(), b.to_f64(), c.to_f64(), d.to_f64());
+    let (adx, ady) = (a.x - d.x, a.y - d.y);
+    let (bdx, bdy) = (b.x - d.x, b.y - d.y);
+    let (cdx, cdy) = (c.x - d.x, c.y - d.y);
+    let (bdxcdy, cdxbdy) = (bdx * cdy, cdx * bdy);
+    let (cdxady, adxcdy) = (cdx * ady, adx * cdy);
+    let (adxbdy, bdxady) = (adx * bdy, bdx * ady);
+    let alift = adx * adx + ady * ady;
+    let blift = bdx * bdx + bdy * bdy;
+    let clift = cdx * cdx + cdy * cdy;
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+    if det.abs() > INCIRCLE_BOUND * permanent {
+        return det;
+    }
+    let (adx, ady) = (difference(a.x, d.x), difference(a.y, d.y));
+    let (bdx, bdy) = (difference(b.x, d.x), difference(b.y, d.y));
+    let (cdx, cdy) = (difference(c.x, d.x), difference(c.y, d.y));
+    let lift = |dx: &[f64], dy: &[f64]| sum(&product(dx, dx), &product(dy, dy));
+    let det = sum(
+        &sum(
+            &product(&lift(&adx, &ady), &cross(&bdx, &bdy, &cdx, &cdy)),
+            &product(&lift(&bdx, &bdy), &cross(&cdx, &cdy, &adx, &ady)),
+        ),
+        &product(&lift(&cdx, &cdy), &cross(&adx, &ady, &bdx, &bdy)),
+    );
+    estimate(&det)
+}
+
+// The exact fallbacks represent each value as an expansion: a sum of floats that don't overlap,
+// ordered from smallest to largest magnitude, so that the last one determines the sign.
+
+/// Returns `a + b` along with the rounding error.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// Like `two_sum`, but requires `|a| >= |b|`.
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    (x, b - (x - a))
+}
+
+/// Returns `a * b` along with the rounding error.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+fn difference(a: f64, b: f64) -> Vec<f64> {
+    let (x, error) = two_sum(a, -b);
+    grow(&[error], x)
+}
+
+/// Adds `b` to the expansion `e`.
+fn grow(e: &[f64], b: f64) -> Vec<f64> {
+    let mut h = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+    for &component in e {
+        let (sum, error) = two_sum(q, component);
+        if error != 0.0 {
+            h.push(error);
+        }
+        q = sum;
+    }
+    if q != 0.0 || h.is_empty() {
+        h.push(q);
+    }
+    h
+}
+
+fn sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter()
+        .fold(e.to_vec(), |sum, component| grow(&sum, *component))
+}
+
+/// Multiplies the expansion `e` by `b`.
+fn scale(e: &[f64], b: f64) -> Vec<f64> {
+    let mut h = Vec::with_capacity(e.len() * 2);
+    let (mut q, error) = two_product(e[0], b);
+    if error != 0.0 {
+        h.push(error);
+    }
+    for &component in &e[1..] {
+        let (product, product_error) = two_product(component, b);
+        let (sum, error) = two_sum(q, product_error);
+        if error != 0.0 {
+            h.push(error);
+        }
+        let (sum, error) = fast_two_sum(product, sum);
+        if error != 0.0 {
+            h.push(error);
+        }
+        q = sum;
+    }
+    if q != 0.0 || h.is_empty() {
+        h.push(q);
+    }
+    h
+}
+
+fn product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(Vec::new(), |product, component| {
+        sum(&product, &scale(e, *component))
+    })
+}
+
+/// Returns `ax * by - ay * bx`.
+fn cross(ax: &[f64], ay: &[f64], bx: &[f64], by: &[f64]) -> Vec<f64> {
+    let negative: Vec<f64> = product(ay, bx).iter().map(|component| -component).collect();
+    sum(&product(ax, by), &negative)
+}
+
+fn estimate(e: &[f64]) -> f64 {
+    e.iter().sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn orientation() {
+        let (a, b) = (Point::new(0.0f32, 0.0), Point::new(4.0, 0.0));
+        // Below the segment is clockwise, as seen on screen.
+        assert!(orient2d(a, b, Point::new(2.0, 1.0)) > 0.0);
+        assert!(orient2d(a, b, Point::new(2.0, -1.0)) < 0.0);
+        assert_eq!(orient2d(a, b, Point::new(8.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn nearly_collinear() {
+        // Points within a few ulps of the line through `b` and `c`, where a plain cross product
+        // often gets the sign wrong. Scaling by 2^53 makes every coordinate an integer, which gives
+        // the exact answer to compare against.
+        let (b, c) = (Point::new(12.0, 12.0), Point::new(24.0, 24.0));
+        let ulp = 2f64.powi(-53);
+        let exact = |x: f64| (x / ulp) as i128;
+        for i in 0..64 {
+            for j in 0..64 {
+                let a = Point::new(0.5 + i as f64 * ulp, 0.5 + j as f64 * ulp);
+                let expected = (exact(b.x) - exact(a.x)) * (exact(c.y) - exact(a.y))
+                    - (exact(b.y) - exact(a.y)) * (exact(c.x) - exact(a.x));
+                let orientation = orient2d(a, b, c);
+                assert_eq!(
+                    orientation.partial_cmp(&0.0),
+                    Some(expected.cmp(&0)),
+                    "{:?}",
+                    a
+                );
+                assert_eq!(
+                    orient2d(b, c, a).partial_cmp(&0.0),
+                    orientation.partial_cmp(&0.0)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn circles() {
+        let (a, b, c) = (
+            Point::new(0.0f32, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+        );
+        assert!(incircle(a, b, c, Point::new(1.0, 1.0)) > 0.0);
+        assert!(incircle(a, b, c, Point::new(3.0, 3.0)) < 0.0);
+        assert_eq!(incircle(a, b, c, Point::new(0.0, 2.0)), 0.0);
+        // Reversing the triangle flips the sign.
+        assert!(incircle(c, b, a, Point::new(1.0, 1.0)) < 0.0);
+    }
+
+    #[test]
+    fn nearly_cocircular() {
+        // The corners of a square far from the origin are exactly cocircular, so nudging the
+        // last one by an ulp has to be detected exactly.
+        let offset = 1_000_000.0;
+        let point = |x: f64, y: f64| Point::new(offset + x, offset + y);
+        let (a, b, c) = (point(0.0, 0.0), point(1.0, 0.0), point(1.0, 1.0));
+        let ulp = 2f64.powi(-33);
+        assert_eq!(incircle(a, b, c, point(0.0, 1.0)), 0.0);
+        assert!(incircle(a, b, c, point(ulp, 1.0)) > 0.0);
+        assert!(incircle(a, b, c, point(0.0, 1.0 + ulp)) < 0.0);
+        assert!(incircle(a, b, c, point(-ulp, 1.0 - ulp)) < 0.0);
+    }
+}
@@ -0,0 +1,375 @@
+use crate::{Circle, Distance, Point, Rect};
+
+const NONE: usize = usize::MAX;
+const ROOT: usize = 0;
+
+/// Identifies an item in a `Quadtree`. Handles are reused once their item is removed.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct QuadtreeHandle(usize);
+
+#[derive(Clone, Debug)]
+struct Node<T> {
+    bounds: Rect<T>,
+    parent: usize,
+    /// The first of four consecutive children, or `NONE` for leaves.
+    children: usize,
+    depth: usize,
+    items: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+struct Entry<V, T> {
+    bounds: Rect<T>,
+    value: V,
+    node: usize,
+}
+
+/// A spatial index over items with `Rect` bounds. Each item lives in the smallest node that fully
+/// contains it, and nodes split into quadrants once they hold too many items. Items outside of the
+/// root bounds are still stored, but they all end up in the root.
+#[derive(Clone, Debug)]
+pub struct Quadtree<V, T = f32> {
+    nodes: Vec<Node<T>>,
+    free_nodes: Vec<usize>,
+    entries: Vec<Option<Entry<V, T>>>,
+    free_entries: Vec<usize>,
+    len: usize,
+    max_items: usize,
+    max_depth: usize,
+}
+
+impl<V, T: en::Float> Quadtree<V, T> {
+    /// Creates a quadtree that splits nodes holding more than 8 items, up to 8 levels deep.
+    pub fn new(bounds: Rect<T>) -> Self {
+        Self::with_limits(bounds, 8, 8)
+    }
+
+    /// Creates a quadtree that splits nodes holding more than `max_items`, up to `max_depth` levels
+    /// below the root.
+    pub fn with_limits(bounds: Rect<T>, max_items: usize, max_depth: usize) -> Self {
+        Self {
+            nodes: vec![Node {
+                bounds,
+                parent: NONE,
+                children: NONE,
+                depth: 0,
+                items: Vec::new(),
+            }],
+            free_nodes: Vec::new(),
+            entries: Vec::new(),
+            free_entries: Vec::new(),
+            len: 0,
+            max_items,
+            max_depth,
+        }
+    }
+
+    pub fn bounds(&self) -> Rect<T> {
+        self.nodes[ROOT].bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::with_limits(self.bounds(), self.max_items, self.max_depth);
+    }
+
+    pub fn get(&self, handle: QuadtreeHandle) -> Option<&V> {
+        self.entry(handle).map(|entry| &entry.value)
+    }
+
+    pub fn get_mut(&mut self, handle: QuadtreeHandle) -> Option<&mut V> {
+        self.entries
+            .get_mut(handle.0)
+            .and_then(Option::as_mut)
+            .map(|entry| &mut entry.value)
+    }
+
+    /// Returns the bounds that the item was inserted or last updated with.
+    pub fn item_bounds(&self, handle: QuadtreeHandle) -> Option<Rect<T>> {
+        self.entry(handle).map(|entry| entry.bounds)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (QuadtreeHandle, &V)> + '_ {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                entry
+                    .as_ref()
+                    .map(|entry| (QuadtreeHandle(index), &entry.value))
+            })
+    }
+
+    pub fn insert(&mut self, bounds: Rect<T>, value: V) -> QuadtreeHandle {
+        let entry = Entry {
+            bounds,
+            value,
+            node: NONE,
+        };
+        let index = if let Some(index) = self.free_entries.pop() {
+            self.entries[index] = Some(entry);
+            index
+        } else {
+            self.entries.push(Some(entry));
+            self.entries.len() - 1
+        };
+        self.len += 1;
+        self.place(index);
+        QuadtreeHandle(index)
+    }
+
+    pub fn remove(&mut self, handle: QuadtreeHandle) -> Option<V> {
+        let entry = self.entries.get_mut(handle.0)?.take()?;
+        self.free_entries.push(handle.0);
+        self.len -= 1;
+        self.unlink(handle.0, entry.node);
+        Some(entry.value)
+    }
+
+    /// Moves the item to `bounds`, returning `false` if there's no such item.
+    pub fn update(&mut self, handle: QuadtreeHandle, bounds: Rect<T>) -> bool {
+        let node = match self.entries.get_mut(handle.0).and_then(Option::as_mut) {
+            Some(entry) => {
+                entry.bounds = bounds;
+                entry.node
+            }
+            None => return false,
+        };
+        // Small moves usually stay within the same node, and then there's nothing else to do.
+        if self.target(&bounds) != node {
+            self.unlink(handle.0, node);
+            self.place(handle.0);
+        }
+        true
+    }
+
+    /// Finds every item whose bounds overlap or touch `rect`.
+    pub fn query_rect(&self, rect: Rect<T>) -> impl Iterator<Item = (QuadtreeHandle, &V)> + '_ {
+        self.query(move |bounds| bounds.intersects(&rect))
+    }
+
+    /// Finds every item whose bounds overlap or touch `circle`.
+    pub fn query_circle(
+        &self,
+        circle: Circle<T>,
+    ) -> impl Iterator<Item = (QuadtreeHandle, &V)> + '_ {
+        self.query(move |bounds| {
+            (bounds.closest_point(circle.center()) - circle.center()).magnitude_squared()
+                <= circle.radius_squared()
+        })
+    }
+
+    /// Finds every item whose bounds contain `point`, including along their edges.
+    pub fn query_point(&self, point: Point<T>) -> impl Iterator<Item = (QuadtreeHandle, &V)> + '_ {
+        self.query(move |bounds| bounds.contains_inclusive(point))
+    }
+
+    fn entry(&self, handle: QuadtreeHandle) -> Option<&Entry<V, T>> {
+        self.entries.get(handle.0).and_then(Option::as_ref)
+    }
+
+    fn bounds_of(&self, index: usize) -> Rect<T> {
+        self.entries[index].as_ref().unwrap().bounds
+    }
+
+    /// Finds the deepest existing node that fully contains `bounds`.
+    fn target(&self, bounds: &Rect<T>) -> usize {
+        let mut node = ROOT;
+        while self.nodes[node].children != NONE {
+            let first = self.nodes[node].children;
+            match (first..first + 4).find(|child| self.nodes[*child].bounds.contains_rect(bounds)) {
+                Some(child) => node = child,
+                None => break,
+            }
+        }
+        node
+    }
+
+    /// Adds an item to the node that should hold it, splitting the node if it gets too full.
+    fn place(&mut self, index: usize) {
+        let node = self.target(&self.bounds_of(index));
+        self.nodes[node].items.push(index);
+        self.entries[index].as_mut().unwrap().node = node;
+        if self.nodes[node].children == NONE
+            && self.nodes[node].items.len() > self.max_items
+            && self.nodes[node].depth < self.max_depth
+        {
+            self.split(node);
+        }
+    }
+
+    fn split(&mut self, node: usize) {
+        let bounds = self.nodes[node].bounds;
+        let center = bounds.center();
+        let quadrants = [
+            Rect::from_top_right_bottom_left(bounds.top(), center.x, center.y, bounds.left()),
+            Rect::from_top_right_bottom_left(bounds.top(), bounds.right(), center.y, center.x),
+            Rect::from_top_right_bottom_left(center.y, center.x, bounds.bottom(), bounds.left()),
+            Rect::from_top_right_bottom_left(center.y, bounds.right(), bounds.bottom(), center.x),
+        ];
+        let depth = self.nodes[node].depth + 1;
+        let children = quadrants.map(|bounds| Node {
+            bounds,
+            parent: node,
+            children: NONE,
+            depth,
+            items: Vec::new(),
+        });
+        let first = if let Some(first) = self.free_nodes.pop() {
+            for (offset, child) in children.into_iter().enumerate() {
+                self.nodes[first + offset] = child;
+            }
+            first
+        } else {
+            self.nodes.extend(children);
+            self.nodes.len() - 4
+        };
+        self.nodes[node].children = first;
+        // Push down whatever fits entirely within a quadrant.
+        for index in std::mem::take(&mut self.nodes[node].items) {
+            let bounds = self.bounds_of(index);
+            let target = (first..first + 4)
+                .find(|child| self.nodes[*child].bounds.contains_rect(&bounds))
+                .unwrap_or(node);
+            self.nodes[target].items.push(index);
+            self.entries[index].as_mut().unwrap().node = target;
+        }
+    }
+
+    /// Detaches an item from its node, merging nodes back together once they're sparse enough.
+    fn unlink(&mut self, index: usize, node: usize) {
+        let items = &mut self.nodes[node].items;
+        let position = items.iter().position(|item| *item == index).unwrap();
+        items.swap_remove(position);
+        let mut node = if self.nodes[node].children == NONE {
+            self.nodes[node].parent
+        } else {
+            node
+        };
+        while node != NONE && self.can_merge(node) {
+            let first = self.nodes[node].children;
+            for child in first..first + 4 {
+                for index in std::mem::take(&mut self.nodes[child].items) {
+                    self.nodes[node].items.push(index);
+                    self.entries[index].as_mut().unwrap().node = node;
+                }
+            }
+            self.nodes[node].children = NONE;
+            self.free_nodes.push(first);
+            node = self.nodes[node].parent;
+        }
+    }
+
+    /// Checks if `node`'s children are all leaves, and that it could hold all of their items.
+    fn can_merge(&self, node: usize) -> bool {
+        let first = self.nodes[node].children;
+        first != NONE
+            && (first..first + 4).all(|child| self.nodes[child].children == NONE)
+            && self.nodes[node].items.len()
+                + (first..first + 4)
+                    .map(|child| self.nodes[child].items.len())
+                    .sum::<usize>()
+                <= self.max_items
+    }
+
+    /// Visits every node whose bounds pass `test`, yielding the items whose bounds pass it too.
+    /// The root is always visited, since it also holds any items outside of its bounds.
+    fn query<'a>(
+        &'a self,
+        test: impl Fn(&Rect<T>) -> bool + 'a,
+    ) -> impl Iterator<Item = (QuadtreeHandle, &'a V)> + 'a {
+        let mut stack = vec![ROOT];
+        let mut items: &[usize] = &[];
+        std::iter::from_fn(move || loop {
+            if let Some((index, rest)) = items.split_first() {
+                items = rest;
+                let entry = self.entries[*index].as_ref().unwrap();
+                if test(&entry.bounds) {
+                    return Some((QuadtreeHandle(*index), &entry.value));
+                }
+            } else {
+                let node = &self.nodes[stack.pop()?];
+                if node.children != NONE {
+                    stack.extend(
+                        (node.children..node.children + 4)
+                            .filter(|child| test(&self.nodes[*child].bounds)),
+                    );
+                }
+                items = &node.items;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::{sorted, square};
+
+    /// A 10x10 grid of unit squares, spaced 10 apart.
+    fn grid() -> (Quadtree<usize>, Vec<QuadtreeHandle>) {
+        let mut tree = Quadtree::with_limits(square(0.0, 0.0, 100.0), 4, 6);
+        let handles = (0..100)
+            .map(|index| {
+                let (x, y) = ((index % 10) as f32 * 10.0, (index / 10) as f32 * 10.0);
+                tree.insert(square(x, y, 1.0), index)
+            })
+            .collect();
+        (tree, handles)
+    }
+
+    #[test]
+    fn queries() {
+        let (tree, _) = grid();
+        assert_eq!(tree.len(), 100);
+        assert!(tree.nodes.len() > 1);
+        assert_eq!(sorted(tree.query_rect(square(5.0, 5.0, 10.0))), vec![11]);
+        assert_eq!(
+            sorted(tree.query_rect(square(0.0, 0.0, 10.0))),
+            vec![0, 1, 10, 11]
+        );
+        assert_eq!(sorted(tree.query_point(Point::new(20.5, 30.5))), vec![32]);
+        assert!(tree.query_point(Point::new(25.0, 25.0)).next().is_none());
+        assert_eq!(
+            sorted(tree.query_circle(Circle::new(Point::new(50.5, 50.5), 10.0))),
+            vec![45, 54, 55, 56, 65]
+        );
+        // Every query matches a linear scan.
+        let query = square(13.0, 27.0, 41.0);
+        let expected: Vec<usize> = tree
+            .iter()
+            .filter(|(handle, _)| tree.item_bounds(*handle).unwrap().intersects(&query))
+            .map(|(_, value)| *value)
+            .collect();
+        assert_eq!(sorted(tree.query_rect(query)), expected);
+    }
+
+    #[test]
+    fn remove_and_update() {
+        let (mut tree, handles) = grid();
+        assert_eq!(tree.remove(handles[11]), Some(11));
+        assert_eq!(tree.remove(handles[11]), None);
+        assert!(tree.query_rect(square(5.0, 5.0, 10.0)).next().is_none());
+        assert!(tree.update(handles[12], square(5.0, 5.0, 1.0)));
+        assert_eq!(sorted(tree.query_rect(square(5.0, 5.0, 10.0))), vec![12]);
+        assert!(tree.query_point(Point::new(20.5, 10.5)).next().is_none());
+        // Items outside of the root bounds can still be found.
+        assert!(tree.update(handles[0], square(-50.0, -50.0, 1.0)));
+        assert_eq!(sorted(tree.query_point(Point::new(-49.5, -49.5))), vec![0]);
+        // Removing everything merges the tree back into a single node.
+        for handle in handles {
+            tree.remove(handle);
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.nodes[ROOT].children, NONE);
+        let handle = tree.insert(square(1.0, 1.0, 1.0), 7);
+        assert_eq!(tree.get(handle), Some(&7));
+    }
+}
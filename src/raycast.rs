@@ -0,0 +1,372 @@
+use crate::{
+    Capsule, Circle, Distance, Ellipse, FillRule, LineSegment, MultiPolygon, Point, Polygon,
+    PolygonWithHoles, Quad, Ray, Rect, Vector,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Where a ray hit a shape.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct RayHit<T = f32> {
+    pub point: Point<T>,
+    /// The unit normal of the surface that was hit, pointing back towards the ray.
+    pub normal: Vector<T>,
+    /// How far the ray traveled before hitting.
+    pub distance: T,
+}
+
+/// A shape that rays can hit. Rays that start inside of a shape never hit it, so that e.g. a
+/// character's line of sight isn't blocked by the character itself.
+pub trait Raycast<T: en::Float> {
+    /// Returns where `ray` first hits the shape, as long as it's no more than `max_distance` away.
+    fn raycast(&self, ray: &Ray<T>, max_distance: T) -> Option<RayHit<T>>;
+}
+
+impl<T: en::Float, S: Raycast<T> + ?Sized> Raycast<T> for &S {
+    fn raycast(&self, ray: &Ray<T>, max_distance: T) -> Option<RayHit<T>> {
+        (**self).raycast(ray, max_distance)
+    }
+}
+
+impl<T: en::Float, S: Raycast<T> + ?Sized> Raycast<T> for Box<S> {
+    fn raycast(&self, ray: &Ray<T>, max_distance: T) -> Option<RayHit<T>> {
+        (**self).raycast(ray, max_distance)
+    }
+}
+
+impl<T: en::Float> Ray<T> {
+    /// Returns where the ray first hits `shape`, as long as it's no more than `max_distance` away.
+    /// Pass `T::infinity()` for a ray that goes on forever.
+    pub fn cast(&self, shape: &(impl Raycast<T> + ?Sized), max_distance: T) -> Option<RayHit<T>> {
+        shape.raycast(self, max_distance)
+    }
+
+    /// Returns the index of the first shape that the ray hits along with where it hit.
+    pub fn cast_nearest<I>(&self, shapes: I, max_distance: T) -> Option<(usize, RayHit<T>)>
+    where
+        I: IntoIterator,
+        I::Item: Raycast<T>,
+    {
+        let mut nearest = None;
+        let mut max_distance = max_distance;
+        for (index, shape) in shapes.into_iter().enumerate() {
+            // Each hit shortens the ray, so that farther shapes can bail out early.
+            if let Some(hit) = shape.raycast(self, max_distance) {
+                max_distance = hit.distance;
+                nearest = Some((index, hit));
+            }
+        }
+        nearest
+    }
+
+    fn hit(&self, distance: T, normal: Vector<T>) -> RayHit<T> {
+        RayHit {
+            point: self.point + self.unit_vector() * distance,
+            normal,
+            distance,
+        }
+    }
+}
+
+/// Finds where a ray crosses a line segment, using whichever of the segment's normals faces the
+/// ray. Rays running parallel to the segment don't hit it.
+fn cast_line_segment<T: en::Float>(
+    ray: &Ray<T>,
+    segment: LineSegment<T>,
+    max_distance: T,
+) -> Option<RayHit<T>> {
+    let direction = ray.unit_vector();
+    let edge = segment.vector();
+    let denominator = direction.cross_product(edge);
+    if denominator == T::zero() {
+        return None;
+    }
+    let offset = segment.from - ray.point;
+    let distance = offset.cross_product(edge) / denominator;
+    let along = offset.cross_product(direction) / denominator;
+    if distance < T::zero() || distance > max_distance || along < T::zero() || along > T::one() {
+        return None;
+    }
+    let normal = Vector::new(edge.dy, -edge.dx).normalize();
+    let normal = if normal.dot_product(direction) > T::zero() {
+        -normal
+    } else {
+        normal
+    };
+    Some(ray.hit(distance, normal))
+}
+
+fn cast_rings<'a, T: en::Float>(
+    ray: &Ray<T>,
+    rings: impl IntoIterator<Item = &'a [Point<T>]>,
+    max_distance: T,
+) -> Option<RayHit<T>> {
+    let mut nearest = None;
+    let mut max_distance = max_distance;
+    for ring in rings {
+        let next = ring.iter().cycle().skip(1);
+        for (from, to) in ring.iter().zip(next) {
+            if let Some(hit) = cast_line_segment(ray, LineSegment::new(*from, *to), max_distance) {
+                max_distance = hit.distance;
+                nearest = Some(hit);
+            }
+        }
+    }
+    nearest
+}
+
+impl<T: en::Float> Raycast<T> for LineSegment<T> {
+    fn raycast(&self, ray: &Ray<T>, max_distance: T) -> Option<RayHit<T>> {
+        cast_line_segment(ray, *self, max_distance)
+    }
+}
+
+impl<T: en::Float> Raycast<T> for Rect<T> {
+    fn raycast(&self, ray: &Ray<T>, max_distance: T) -> Option<RayHit<T>> {
+        let direction = ray.unit_vector();
+        // Returns the distances at which the ray enters and exits the rect's span along one axis.
+        let span = |min: T, max: T, origin: T, speed: T| {
+            if speed == T::zero() {
+                if origin >= min && origin <= max {
+                    Some((T::neg_infinity(), T::infinity()))
+                } else {
+                    None
+                }
+            } else if speed > T::zero() {
+                Some(((min - origin) / speed, (max - origin) / speed))
+            } else {
+                Some(((max - origin) / speed, (min - origin) / speed))
+            }
+        };
+        let (enter_x, exit_x) = span(self.left(), self.right(), ray.point.x, direction.dx)?;
+        let (enter_y, exit_y) = span(self.top(), self.bottom(), ray.point.y, direction.dy)?;
+        let (distance, normal) = if enter_x >= enter_y {
+            let sign = if direction.dx > T::zero() {
+                -T::one()
+            } else {
+                T::one()
+            };
+            (enter_x, Vector::from_dx(sign))
+        } else {
+            let sign = if direction.dy > T::zero() {
+                -T::one()
+            } else {
+                T::one()
+            };
+            (enter_y, Vector::from_dy(sign))
+        };
+        let exit = en::Num::min(exit_x, exit_y);
+        if distance < T::zero() || distance > max_distance || distance > exit {
+            return None;
+        }
+        Some(ray.hit(distance, normal))
+    }
+}
+
+impl<T: en::Float> Raycast<T> for Quad<T> {
+    fn raycast(&self, ray: &Ray<T>, max_distance: T) -> Option<RayHit<T>> {
+        Polygon::from(*self).raycast(ray, max_distance)
+    }
+}
+
+impl<T: en::Float> Raycast<T> for Polygon<T> {
+    fn raycast(&self, ray: &Ray<T>, max_distance: T) -> Option<RayHit<T>> {
+        if self.contains(ray.point, FillRule::NonZero) {
+            return None;
+        }
+        cast_rings(ray, std::iter::once(self.points()), max_distance)
+    }
+}
+
+impl<T: en::Float> Raycast<T> for PolygonWithHoles<T> {
+    fn raycast(&self, ray: &Ray<T>, max_distance: T) -> Option<RayHit<T>> {
+        if self.contains(ray.point) {
+            return None;
+        }
+        cast_rings(ray, self.rings().map(Polygon::points), max_distance)
+    }
+}
+
+impl<T: en::Float> Raycast<T> for MultiPolygon<T> {
+    fn raycast(&self, ray: &Ray<T>, max_distance: T) -> Option<RayHit<T>> {
+        if self.contains(ray.point) {
+            return None;
+        }
+        cast_rings(ray, self.rings().map(Polygon::points), max_distance)
+    }
+}
+
+impl<T: en::Float> Raycast<T> for Circle<T> {
+    fn raycast(&self, ray: &Ray<T>, max_distance: T) -> Option<RayHit<T>> {
+        let direction = ray.unit_vector();
+        let offset = ray.point - self.center();
+        // Solve for where the ray is `radius` away from the center.
+        let b = offset.dot_product(direction);
+        let c = offset.magnitude_squared() - self.radius_squared();
+        if c < T::zero() {
+            return None;
+        }
+        let discriminant = b * b - c;
+        if discriminant < T::zero() {
+            return None;
+        }
+        let distance = -b - discriminant.sqrt();
+        if distance < T::zero() || distance > max_distance {
+            return None;
+        }
+        Some(ray.hit(distance, (offset + direction * distance).normalize()))
+    }
+}
+
+impl<T: en::Float> Raycast<T> for Ellipse<T> {
+    fn raycast(&self, ray: &Ray<T>, max_distance: T) -> Option<RayHit<T>> {
+        let radius = self.radius();
+        if radius.width() == T::zero() || radius.height() == T::zero() {
+            return None;
+        }
+        // Squash everything so that the ellipse becomes a unit circle.
+        let squash = |vector: Vector<T>| {
+            Vector::new(vector.dx / radius.width(), vector.dy / radius.height())
+        };
+        let direction = squash(ray.unit_vector());
+        let offset = squash(ray.point - self.center());
+        let a = direction.magnitude_squared();
+        let b = offset.dot_product(direction);
+        let c = offset.magnitude_squared() - T::one();
+        if c < T::zero() {
+            return None;
+        }
+        let discriminant = b * b - a * c;
+        if discriminant < T::zero() {
+            return None;
+        }
+        let distance = (-b - discriminant.sqrt()) / a;
+        if distance < T::zero() || distance > max_distance {
+            return None;
+        }
+        let hit = ray.hit(distance, Vector::zero());
+        Some(RayHit {
+            normal: self.outline_normal(hit.point),
+            ..hit
+        })
+    }
+}
+
+impl<T: en::Float> Raycast<T> for Capsule<T> {
+    fn raycast(&self, ray: &Ray<T>, max_distance: T) -> Option<RayHit<T>> {
+        let nearest = self.segment.closest_point(ray.point);
+        if (ray.point - nearest).magnitude_squared() < self.radius * self.radius {
+            return None;
+        }
+        // The capsule's outline is made of two flat sides and two round ends.
+        let vector = self.segment.vector();
+        let mut sides = Vec::with_capacity(2);
+        if vector != Vector::zero() {
+            let offset = Vector::new(vector.dy, -vector.dx).normalize() * self.radius;
+            sides.push(self.segment.map(|point| point + offset));
+            sides.push(self.segment.map(|point| point - offset));
+        }
+        let side = ray.cast_nearest(sides, max_distance).map(|(_, hit)| hit);
+        let max_distance = side.map_or(max_distance, |hit| hit.distance);
+        let ends = self
+            .segment
+            .points()
+            .map(|point| Circle::new(point, self.radius));
+        ray.cast_nearest(ends, max_distance)
+            .map(|(_, hit)| hit)
+            .or(side)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, Angle, Size, Transform};
+
+    fn ray(x: f32, y: f32, dx: f32, dy: f32) -> Ray {
+        Ray::new(Point::new(x, y), Vector::new(dx, dy).angle())
+    }
+
+    fn assert_hit(hit: Option<RayHit>, point: Point, normal: Vector, distance: f32) {
+        let hit = hit.unwrap();
+        assert_approx_eq!(hit.point.x, point.x);
+        assert_approx_eq!(hit.point.y, point.y);
+        assert_approx_eq!(hit.normal.dx, normal.dx);
+        assert_approx_eq!(hit.normal.dy, normal.dy);
+        assert_approx_eq!(hit.distance, distance);
+    }
+
+    #[test]
+    fn rects() {
+        let rect = Rect::from_top_left(Point::new(2.0, -1.0), Size::new(2.0, 2.0));
+        let hit = ray(0.0, 0.0, 1.0, 0.0).cast(&rect, f32::INFINITY);
+        assert_hit(hit, Point::new(2.0, 0.0), Vector::new(-1.0, 0.0), 2.0);
+        let hit = ray(3.0, 5.0, 0.0, -1.0).cast(&rect, f32::INFINITY);
+        assert_hit(hit, Point::new(3.0, 1.0), Vector::new(0.0, 1.0), 4.0);
+        // Too short, pointing the wrong way, or starting inside.
+        assert!(ray(0.0, 0.0, 1.0, 0.0).cast(&rect, 1.5).is_none());
+        assert!(ray(0.0, 0.0, -1.0, 0.0).cast(&rect, 10.0).is_none());
+        assert!(ray(3.0, 0.0, 1.0, 0.0).cast(&rect, 10.0).is_none());
+    }
+
+    #[test]
+    fn round_shapes() {
+        let circle = Circle::new(Point::new(5.0, 0.0), 1.0);
+        let hit = ray(0.0, 0.0, 1.0, 0.0).cast(&circle, f32::INFINITY);
+        assert_hit(hit, Point::new(4.0, 0.0), Vector::new(-1.0, 0.0), 4.0);
+        assert!(ray(0.0, 2.0, 1.0, 0.0).cast(&circle, 10.0).is_none());
+
+        let ellipse = Ellipse::new(Point::new(0.0, 5.0), Size::new(3.0, 2.0));
+        let hit = ray(0.0, 0.0, 0.0, 1.0).cast(&ellipse, f32::INFINITY);
+        assert_hit(hit, Point::new(0.0, 3.0), Vector::new(0.0, -1.0), 3.0);
+
+        let capsule = Capsule::from_points(Point::new(0.0, 5.0), Point::new(0.0, 10.0), 1.0);
+        let hit = ray(-5.0, 7.0, 1.0, 0.0).cast(&capsule, f32::INFINITY);
+        assert_hit(hit, Point::new(-1.0, 7.0), Vector::new(-1.0, 0.0), 4.0);
+        let hit = ray(0.0, 0.0, 0.0, 1.0).cast(&capsule, f32::INFINITY);
+        assert_hit(hit, Point::new(0.0, 4.0), Vector::new(0.0, -1.0), 4.0);
+    }
+
+    #[test]
+    fn polygons() {
+        let diamond = Rect::from_center(Point::zero(), Size::new(2.0, 2.0))
+            .transform(Transform::from_rotation(Angle::FRAC_PI_4(), Point::zero()));
+        let hit = ray(-5.0, 0.5, 1.0, 0.0).cast(&diamond, f32::INFINITY);
+        let sqrt_half = 0.5f32.sqrt();
+        assert_hit(
+            hit,
+            Point::new(0.5 - 2f32.sqrt(), 0.5),
+            Vector::new(-sqrt_half, sqrt_half),
+            5.5 - 2f32.sqrt(),
+        );
+
+        let ring = PolygonWithHoles::new(
+            Rect::from_center(Point::zero(), Size::new(10.0, 10.0)).into(),
+            vec![Rect::from_center(Point::zero(), Size::new(4.0, 4.0)).into()],
+        );
+        // Starting in the hole, the ray hits the inside of the hole.
+        let hit = ray(0.0, 0.0, 1.0, 0.0).cast(&ring, f32::INFINITY);
+        assert_hit(hit, Point::new(2.0, 0.0), Vector::new(-1.0, 0.0), 2.0);
+        assert!(ray(3.0, 0.0, 1.0, 0.0).cast(&ring, f32::INFINITY).is_none());
+    }
+
+    #[test]
+    fn nearest() {
+        let shapes: Vec<Box<dyn Raycast<f32>>> = vec![
+            Box::new(Circle::new(Point::new(8.0, 0.0), 1.0)),
+            Box::new(Rect::from_center(Point::new(4.0, 0.0), Size::new(2.0, 2.0))),
+            Box::new(LineSegment::new(
+                Point::new(10.0, -1.0),
+                Point::new(10.0, 1.0),
+            )),
+        ];
+        let (index, hit) = ray(0.0, 0.0, 1.0, 0.0)
+            .cast_nearest(&shapes, f32::INFINITY)
+            .unwrap();
+        assert_eq!(index, 1);
+        assert_approx_eq!(hit.distance, 3.0);
+        assert!(ray(0.0, 0.0, 1.0, 0.0).cast_nearest(&shapes, 2.0).is_none());
+    }
+}
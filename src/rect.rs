@@ -254,6 +254,10 @@ impl<T: en::Num> Rect<T> {
         Size::new(self.width(), self.height())
     }
 
+    pub fn area(&self) -> T {
+        self.size().area()
+    }
+
     pub fn aspect_ratio(&self) -> T
     where
         T: en::Float,
@@ -533,6 +537,23 @@ impl<T: en::Num> Rect<T> {
         Self::from_top_right_bottom_left(top, right, bottom, left)
     }
 
+    /// Returns `true` if the rects overlap or touch. Unlike `intersection`, this works for rects
+    /// that have no area.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.left <= other.right
+            && other.left <= self.right
+            && self.top <= other.bottom
+            && other.top <= self.bottom
+    }
+
+    /// Returns `true` if `other` is entirely inside this rect, including along its edges.
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        self.left <= other.left
+            && other.right <= self.right
+            && self.top <= other.top
+            && other.bottom <= self.bottom
+    }
+
     pub fn width_slice(&self, num_items: usize, index: usize) -> Self {
         self.width_slice_with_margin(num_items, index, T::zero())
     }
@@ -884,6 +905,19 @@ mod test {
         );
     }
 
+    #[test]
+    fn intersects() {
+        let a = Rect::from_top_left(Point::zero(), Size::square(10));
+        assert!(a.intersects(&Rect::from_top_left(Point::new(5, 5), Size::square(10))));
+        // Touching counts, even though there's no intersection with any area.
+        let touching = Rect::from_top_left(Point::new(10, 0), Size::square(10));
+        assert!(a.intersects(&touching));
+        assert_eq!(a.intersection(&touching), None);
+        assert!(!a.intersects(&Rect::from_top_left(Point::new(11, 0), Size::square(10))));
+        assert!(a.contains_rect(&Rect::from_top_left(Point::new(2, 2), Size::square(8))));
+        assert!(!a.contains_rect(&Rect::from_top_left(Point::new(2, 2), Size::square(9))));
+    }
+
     #[test]
     fn grow_to() {
         let rect = Rect::from_top_left(Point::new(10, 10), Size::new(10, 10));
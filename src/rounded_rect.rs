@@ -0,0 +1,40 @@
+use crate::{Point, Rect, Sdf};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A `Rect` with each corner rounded off by a quarter circle of `radius`. The radius is limited to
+/// half of the rect's shorter side.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct RoundedRect<T = f32> {
+    pub rect: Rect<T>,
+    pub radius: T,
+}
+
+impl<T: en::Num> RoundedRect<T> {
+    pub fn new(rect: Rect<T>, radius: T) -> Self {
+        Self { rect, radius }
+    }
+
+    pub fn bounding_rect(&self) -> Rect<T> {
+        self.rect
+    }
+
+    pub fn map<U: en::Num>(self, f: impl FnOnce(Rect<T>, T) -> (Rect<U>, U)) -> RoundedRect<U> {
+        let (rect, radius) = f(self.rect, self.radius);
+        RoundedRect::new(rect, radius)
+    }
+
+    pub fn cast<U: en::Num>(self) -> RoundedRect<U> {
+        self.map(move |rect, radius| (rect.cast(), en::cast(radius)))
+    }
+
+    impl_casts!(RoundedRect);
+}
+
+impl<T: en::Float> RoundedRect<T> {
+    pub fn contains(&self, point: Point<T>) -> bool {
+        self.signed_distance(point) <= T::zero()
+    }
+}
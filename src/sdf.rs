@@ -0,0 +1,353 @@
+use crate::{
+    Capsule, Circle, Distance, Ellipse, FillRule, HalfPlane, LineSegment, MultiPolygon, Point,
+    Polygon, PolygonWithHoles, Rect, RoundedRect,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A shape described by its signed distance function, which is negative inside the shape, zero on
+/// its outline, and positive outside. SDFs can be combined with the `sdf_` methods, which build
+/// new SDFs out of existing ones.
+pub trait Sdf<T: en::Float> {
+    fn signed_distance(&self, point: Point<T>) -> T;
+
+    /// Covers everything that's inside of either shape.
+    fn sdf_union<S: Sdf<T>>(self, other: S) -> SdfUnion<Self, S>
+    where
+        Self: Sized,
+    {
+        SdfUnion { a: self, b: other }
+    }
+
+    /// Covers everything that's inside of both shapes.
+    fn sdf_intersection<S: Sdf<T>>(self, other: S) -> SdfIntersection<Self, S>
+    where
+        Self: Sized,
+    {
+        SdfIntersection { a: self, b: other }
+    }
+
+    /// Covers everything that's inside of this shape but not `other`.
+    fn sdf_subtraction<S: Sdf<T>>(self, other: S) -> SdfSubtraction<Self, S>
+    where
+        Self: Sized,
+    {
+        SdfSubtraction { a: self, b: other }
+    }
+
+    /// Like `sdf_union`, but blends the shapes together wherever they're within `smoothness` of
+    /// each other, rather than leaving a sharp crease.
+    fn sdf_smooth_union<S: Sdf<T>>(self, other: S, smoothness: T) -> SdfSmoothUnion<Self, S, T>
+    where
+        Self: Sized,
+    {
+        SdfSmoothUnion {
+            a: self,
+            b: other,
+            smoothness,
+        }
+    }
+
+    /// Grows the shape by `radius` in every direction, which rounds off its corners.
+    fn sdf_round(self, radius: T) -> SdfRound<Self, T>
+    where
+        Self: Sized,
+    {
+        SdfRound {
+            shape: self,
+            radius,
+        }
+    }
+}
+
+impl<T: en::Float, S: Sdf<T> + ?Sized> Sdf<T> for &S {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        (**self).signed_distance(point)
+    }
+}
+
+impl<T: en::Float, S: Sdf<T> + ?Sized> Sdf<T> for Box<S> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        (**self).signed_distance(point)
+    }
+}
+
+/// The signed distance from a rect with the given center and half extents.
+fn rect_distance<T: en::Float>(
+    center: Point<T>,
+    half_width: T,
+    half_height: T,
+    point: Point<T>,
+) -> T {
+    let dx = (point.x - center.x).abs() - half_width;
+    let dy = (point.y - center.y).abs() - half_height;
+    let (outside_x, outside_y) = (en::Num::max(dx, T::zero()), en::Num::max(dy, T::zero()));
+    outside_x.hypot(outside_y) + en::Num::min(en::Num::max(dx, dy), T::zero())
+}
+
+/// The signed distance from the outline of a polygon-like shape, where `inside` decides the sign.
+fn ring_distance<'a, T: en::Float + 'a>(
+    rings: impl Iterator<Item = &'a Polygon<T>>,
+    inside: bool,
+    point: Point<T>,
+) -> T {
+    let distance = rings
+        .flat_map(Polygon::line_segments)
+        .fold(T::infinity(), |distance, segment| {
+            en::Num::min(distance, segment.distance_to(point))
+        });
+    if inside {
+        -distance
+    } else {
+        distance
+    }
+}
+
+impl<T: en::Float> Sdf<T> for Circle<T> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        (point - self.center()).magnitude() - self.radius()
+    }
+}
+
+impl<T: en::Float> Sdf<T> for Ellipse<T> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        let distance = (point - self.closest_outline_point(point)).magnitude();
+        let offset = point - self.center();
+        let radius = self.radius();
+        let (x, y) = (offset.dx / radius.width(), offset.dy / radius.height());
+        if x * x + y * y < T::one() {
+            -distance
+        } else {
+            distance
+        }
+    }
+}
+
+impl<T: en::Float> Sdf<T> for Rect<T> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        rect_distance(
+            self.center(),
+            self.width() / T::two(),
+            self.height() / T::two(),
+            point,
+        )
+    }
+}
+
+impl<T: en::Float> Sdf<T> for RoundedRect<T> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        let (half_width, half_height) =
+            (self.rect.width() / T::two(), self.rect.height() / T::two());
+        let radius = en::Num::max(
+            en::Num::min(self.radius, en::Num::min(half_width, half_height)),
+            T::zero(),
+        );
+        rect_distance(
+            self.rect.center(),
+            half_width - radius,
+            half_height - radius,
+            point,
+        ) - radius
+    }
+}
+
+impl<T: en::Float> Sdf<T> for Capsule<T> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        self.segment.distance_to(point) - self.radius
+    }
+}
+
+impl<T: en::Float> Sdf<T> for HalfPlane<T> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        -self.line.signed_distance(point)
+    }
+}
+
+/// Segments have no inside, so this is never negative.
+impl<T: en::Float> Sdf<T> for LineSegment<T> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        self.distance_to(point)
+    }
+}
+
+impl<T: en::Float> Sdf<T> for Polygon<T> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        ring_distance(
+            std::iter::once(self),
+            self.contains(point, FillRule::NonZero),
+            point,
+        )
+    }
+}
+
+impl<T: en::Float> Sdf<T> for PolygonWithHoles<T> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        ring_distance(self.rings(), self.contains(point), point)
+    }
+}
+
+impl<T: en::Float> Sdf<T> for MultiPolygon<T> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        ring_distance(self.rings(), self.contains(point), point)
+    }
+}
+
+/// Created by `Sdf::sdf_union`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SdfUnion<A, B> {
+    pub a: A,
+    pub b: B,
+}
+
+impl<T: en::Float, A: Sdf<T>, B: Sdf<T>> Sdf<T> for SdfUnion<A, B> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        en::Num::min(self.a.signed_distance(point), self.b.signed_distance(point))
+    }
+}
+
+/// Created by `Sdf::sdf_intersection`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SdfIntersection<A, B> {
+    pub a: A,
+    pub b: B,
+}
+
+impl<T: en::Float, A: Sdf<T>, B: Sdf<T>> Sdf<T> for SdfIntersection<A, B> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        en::Num::max(self.a.signed_distance(point), self.b.signed_distance(point))
+    }
+}
+
+/// Created by `Sdf::sdf_subtraction`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SdfSubtraction<A, B> {
+    pub a: A,
+    pub b: B,
+}
+
+impl<T: en::Float, A: Sdf<T>, B: Sdf<T>> Sdf<T> for SdfSubtraction<A, B> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        en::Num::max(
+            self.a.signed_distance(point),
+            -self.b.signed_distance(point),
+        )
+    }
+}
+
+/// Created by `Sdf::sdf_smooth_union`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SdfSmoothUnion<A, B, T = f32> {
+    pub a: A,
+    pub b: B,
+    pub smoothness: T,
+}
+
+impl<T: en::Float, A: Sdf<T>, B: Sdf<T>> Sdf<T> for SdfSmoothUnion<A, B, T> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        let (a, b) = (self.a.signed_distance(point), self.b.signed_distance(point));
+        if self.smoothness <= T::zero() {
+            return en::Num::min(a, b);
+        }
+        // This is the polynomial smooth minimum from Inigo Quilez.
+        let h = en::Num::max(T::one() - (a - b).abs() / self.smoothness, T::zero());
+        en::Num::min(a, b) - h * h * self.smoothness / en::cast(4)
+    }
+}
+
+/// Created by `Sdf::sdf_round`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SdfRound<S, T = f32> {
+    pub shape: S,
+    pub radius: T,
+}
+
+impl<T: en::Float, S: Sdf<T>> Sdf<T> for SdfRound<S, T> {
+    fn signed_distance(&self, point: Point<T>) -> T {
+        self.shape.signed_distance(point) - self.radius
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, Size};
+
+    #[test]
+    fn primitives() {
+        let circle = Circle::new(Point::new(1.0, 1.0), 2.0);
+        assert_approx_eq!(circle.signed_distance(Point::new(1.0, 1.0)), -2.0);
+        assert_approx_eq!(circle.signed_distance(Point::new(1.0, 6.0)), 3.0);
+
+        let rect = Rect::from_top_left(Point::zero(), Size::new(4.0, 2.0));
+        assert_approx_eq!(rect.signed_distance(Point::new(2.0, 1.0)), -1.0);
+        assert_approx_eq!(rect.signed_distance(Point::new(7.0, 6.0)), 5.0);
+        assert_approx_eq!(rect.signed_distance(Point::new(2.0, -3.0)), 3.0);
+        // A polygon with the same outline has the same distances.
+        let polygon = Polygon::from(rect);
+        for point in [
+            Point::new(2.0, 1.0),
+            Point::new(7.0, 6.0),
+            Point::new(0.5, 1.5),
+        ] {
+            assert_approx_eq!(polygon.signed_distance(point), rect.signed_distance(point));
+        }
+
+        let rounded = RoundedRect::new(rect, 0.5);
+        assert_approx_eq!(rounded.signed_distance(Point::new(2.0, -1.0)), 1.0);
+        // The corners are cut off.
+        let corner = Point::new(4.0, 2.0);
+        assert_approx_eq!(rounded.signed_distance(corner), 0.5f32.hypot(0.5) - 0.5);
+        assert!(!rounded.contains(corner));
+        // The radius can't exceed half of the shorter side.
+        assert_approx_eq!(
+            RoundedRect::new(rect, 5.0).signed_distance(Point::new(2.0, 1.0)),
+            -1.0
+        );
+
+        let half_plane = HalfPlane::from_points(Point::new(0.0, 1.0), Point::new(1.0, 1.0));
+        assert_approx_eq!(half_plane.signed_distance(Point::new(0.0, 3.0)), -2.0);
+
+        let capsule = Capsule::from_points(Point::new(0.0, 0.0), Point::new(4.0, 0.0), 1.0);
+        assert_approx_eq!(capsule.signed_distance(Point::new(2.0, 0.5)), -0.5);
+        let segment = LineSegment::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+        assert_approx_eq!(segment.signed_distance(Point::new(6.0, 0.0)), 2.0);
+    }
+
+    #[test]
+    fn ellipses() {
+        let ellipse = Ellipse::new(Point::zero(), Size::new(4.0, 2.0));
+        assert_approx_eq!(ellipse.signed_distance(Point::zero()), -2.0);
+        assert_approx_eq!(ellipse.signed_distance(Point::new(6.0, 0.0)), 2.0);
+        assert_approx_eq!(ellipse.signed_distance(Point::new(0.0, 1.0)), -1.0);
+        assert_approx_eq!(ellipse.signed_distance(Point::new(0.0, 2.0)), 0.0);
+    }
+
+    #[test]
+    fn combinators() {
+        let a = Circle::new(Point::new(0.0, 0.0), 2.0);
+        let b = Circle::new(Point::new(3.0, 0.0), 2.0);
+        let point = Point::new(-1.0, 0.0);
+        assert_approx_eq!(a.sdf_union(b).signed_distance(point), -1.0);
+        assert_approx_eq!(a.sdf_intersection(b).signed_distance(point), 2.0);
+        assert_approx_eq!(a.sdf_subtraction(b).signed_distance(point), -1.0);
+        assert_approx_eq!(
+            a.sdf_subtraction(b).signed_distance(Point::new(1.5, 0.0)),
+            0.5
+        );
+        assert_approx_eq!(a.sdf_round(1.0).signed_distance(point), -2.0);
+        // Blending only matters where the shapes are close to each other.
+        let smooth = a.sdf_smooth_union(b, 1.0);
+        assert_approx_eq!(smooth.signed_distance(point), -1.0);
+        let middle = Point::new(1.5, 0.0);
+        assert!(smooth.signed_distance(middle) < a.sdf_union(b).signed_distance(middle));
+        // Combinators can be nested, and work on references.
+        let rect = Rect::from_top_left(Point::new(-1.0, -1.0), Size::new(2.0, 2.0));
+        let nested = (&a).sdf_union(&b).sdf_subtraction(rect);
+        assert_approx_eq!(nested.signed_distance(Point::zero()), 1.0);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{Cardinal, HorizontalLocation, Impact, Point, Rect, Vector, VerticalLocation};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+
+/// A collider that got in the way during `Rect::move_and_slide`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct SlideContact<T = f32> {
+    /// The index of the collider that was hit.
+    pub index: usize,
+    /// The side of the moving rect that hit it.
+    pub side: Cardinal,
+    /// Where the two rects touched.
+    pub point: Point<T>,
+}
+
+/// The outcome of `Rect::move_and_slide`.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Slide<T = f32> {
+    /// Where the rect ended up.
+    pub rect: Rect<T>,
+    /// Every collider that was hit, in the order they were hit.
+    pub contacts: Vec<SlideContact<T>>,
+    /// The sides of the rect that hit something, without duplicates.
+    pub sides: Vec<Cardinal>,
+}
+
+impl<T> Slide<T> {
+    /// Checks if the given side of the rect hit something, e.g. `Cardinal::South` when landing on
+    /// the ground.
+    pub fn touched(&self, side: Cardinal) -> bool {
+        self.sides.contains(&side)
+    }
+}
+
+impl<T: en::Float> Rect<T> {
+    /// Moves the rect by `displacement`, stopping at any of the `colliders` in the way and sliding
+    /// along them for whatever movement remains. Rects that are already overlapping the moving rect
+    /// are ignored.
+    ///
+    /// Sliding along a surface doesn't count as touching it, so a rect resting on the ground only
+    /// touches its `Cardinal::South` side if the displacement pushes it down, e.g. due to gravity.
+    pub fn move_and_slide<I>(&self, displacement: Vector<T>, colliders: I) -> Slide<T>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Rect<T>>,
+    {
+        let colliders: Vec<Rect<T>> = colliders
+            .into_iter()
+            .map(|collider| *collider.borrow())
+            .collect();
+        let mut rect = *self;
+        let mut remaining = displacement;
+        let mut contacts = Vec::new();
+        // Each impact stops the movement along one axis, so this can only happen twice.
+        while remaining != Vector::zero() {
+            let hit = colliders
+                .iter()
+                .enumerate()
+                .filter_map(|(index, collider)| {
+                    rect.sweep_rect(remaining, collider)
+                        .map(|impact| (index, impact))
+                })
+                .fold(
+                    None,
+                    |earliest: Option<(usize, Impact<T>)>, hit| match earliest {
+                        Some((_, impact)) if impact.time <= hit.1.time => earliest,
+                        _ => Some(hit),
+                    },
+                );
+            let (index, impact) = match hit {
+                Some(hit) => hit,
+                None => {
+                    rect = rect.translate(remaining);
+                    break;
+                }
+            };
+            rect = rect.translate(remaining * impact.time);
+            remaining *= T::one() - impact.time;
+            // Snap to the collider exactly, so that rounding error can't leave the rects
+            // overlapping and snagging on the seams between neighboring colliders.
+            let collider = &colliders[index];
+            let side = if impact.normal.dx < T::zero() {
+                rect = rect.with_x(collider.left(), HorizontalLocation::Right);
+                Cardinal::East
+            } else if impact.normal.dx > T::zero() {
+                rect = rect.with_x(collider.right(), HorizontalLocation::Left);
+                Cardinal::West
+            } else if impact.normal.dy < T::zero() {
+                rect = rect.with_y(collider.top(), VerticalLocation::Bottom);
+                Cardinal::South
+            } else {
+                rect = rect.with_y(collider.bottom(), VerticalLocation::Top);
+                Cardinal::North
+            };
+            if impact.normal.dx != T::zero() {
+                remaining.dx = T::zero();
+            } else {
+                remaining.dy = T::zero();
+            }
+            contacts.push(SlideContact {
+                index,
+                side,
+                point: impact.point,
+            });
+        }
+        let mut sides: Vec<_> = contacts.iter().map(|contact| contact.side).collect();
+        sides.sort();
+        sides.dedup();
+        Slide {
+            rect,
+            contacts,
+            sides,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::rect;
+
+    #[test]
+    fn landing() {
+        let floor = [rect(-10.0, 4.0, 10.0, 1.0), rect(0.0, 4.0, 10.0, 1.0)];
+        let player = rect(-2.0, 0.0, 1.0, 2.0);
+        let slide = player.move_and_slide(Vector::new(4.0, 4.0), floor);
+        assert_eq!(slide.rect, rect(2.0, 2.0, 1.0, 2.0));
+        assert!(slide.touched(Cardinal::South));
+        assert_eq!(slide.sides, vec![Cardinal::South]);
+        assert_eq!(slide.contacts.len(), 1);
+
+        // Running across the seam between the floor tiles with gravity doesn't snag.
+        let slide = slide.rect.move_and_slide(Vector::new(-5.0, 0.5), floor);
+        assert_eq!(slide.rect, rect(-3.0, 2.0, 1.0, 2.0));
+        assert_eq!(slide.sides, vec![Cardinal::South]);
+
+        // Without gravity, it's not touching anything.
+        let slide = slide.rect.move_and_slide(Vector::new(1.0, 0.0), floor);
+        assert!(slide.contacts.is_empty());
+    }
+
+    #[test]
+    fn walls() {
+        let colliders = [
+            rect(0.0, 10.0, 10.0, 1.0),
+            rect(5.0, 0.0, 1.0, 10.0),
+            rect(0.0, -1.0, 10.0, 1.0),
+        ];
+        let player = rect(1.0, 6.0, 1.0, 1.0);
+        let slide = player.move_and_slide(Vector::new(8.0, -8.0), colliders);
+        assert_eq!(slide.rect, rect(4.0, 0.0, 1.0, 1.0));
+        assert_eq!(slide.sides, vec![Cardinal::North, Cardinal::East]);
+        assert_eq!(slide.contacts[0].index, 1);
+        assert_eq!(slide.contacts[0].side, Cardinal::East);
+        assert_eq!(slide.contacts[1].index, 2);
+        assert_eq!(slide.contacts[1].side, Cardinal::North);
+    }
+}
@@ -0,0 +1,425 @@
+use crate::{Distance, Point, Rect, Size};
+use std::collections::HashMap;
+
+/// Identifies an item in a `SpatialHash`. Handles are reused once their item is removed.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SpatialHashHandle(usize);
+
+/// Items covering more cells than this are kept out of the grid, since linking them into every
+/// cell would cost more than checking them against every query.
+const MAX_ITEM_CELLS: u128 = 64;
+
+type CellRange = (Point<i64>, Point<i64>);
+
+#[derive(Clone, Debug)]
+struct Entry<V, T> {
+    bounds: Rect<T>,
+    value: V,
+    /// The first and last cells that the bounds overlap, or `None` for items too big for the grid
+    /// (or without finite bounds), which are kept in `oversized` instead.
+    cells: Option<CellRange>,
+}
+
+/// A spatial index that buckets items into a uniform grid of square cells, which suits lots of
+/// similarly sized objects that move every frame. Only cells that hold something take up memory, so
+/// the grid is unbounded.
+#[derive(Clone, Debug)]
+pub struct SpatialHash<V, T = f32> {
+    cell_size: T,
+    cells: HashMap<Point<i64>, Vec<usize>>,
+    oversized: Vec<usize>,
+    entries: Vec<Option<Entry<V, T>>>,
+    free_entries: Vec<usize>,
+    len: usize,
+}
+
+impl<V, T: en::Float> SpatialHash<V, T> {
+    /// Creates an empty spatial hash. For best results, `cell_size` should be a bit larger than the
+    /// typical item.
+    pub fn new(cell_size: T) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            oversized: Vec::new(),
+            entries: Vec::new(),
+            free_entries: Vec::new(),
+            len: 0,
+        }
+    }
+
+    pub fn cell_size(&self) -> T {
+        self.cell_size
+    }
+
+    /// Returns the cell containing `point`, or `None` if `point` isn't finite or lies too far out
+    /// for a cell to be numbered.
+    pub fn cell(&self, point: Point<T>) -> Option<Point<i64>> {
+        let cell = |coord: T| (coord / self.cell_size).floor().to_i64();
+        Some(Point::new(cell(point.x)?, cell(point.y)?))
+    }
+
+    /// Returns the area covered by `cell`.
+    pub fn cell_rect(&self, cell: Point<i64>) -> Rect<T> {
+        Rect::from_top_left(
+            cell.cast::<T>() * self.cell_size,
+            Size::square(self.cell_size),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.oversized.clear();
+        self.entries.clear();
+        self.free_entries.clear();
+        self.len = 0;
+    }
+
+    pub fn get(&self, handle: SpatialHashHandle) -> Option<&V> {
+        self.entry(handle).map(|entry| &entry.value)
+    }
+
+    pub fn get_mut(&mut self, handle: SpatialHashHandle) -> Option<&mut V> {
+        self.entries
+            .get_mut(handle.0)
+            .and_then(Option::as_mut)
+            .map(|entry| &mut entry.value)
+    }
+
+    /// Returns the bounds that the item was inserted or last updated with.
+    pub fn item_bounds(&self, handle: SpatialHashHandle) -> Option<Rect<T>> {
+        self.entry(handle).map(|entry| entry.bounds)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (SpatialHashHandle, &V)> + '_ {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                entry
+                    .as_ref()
+                    .map(|entry| (SpatialHashHandle(index), &entry.value))
+            })
+    }
+
+    pub fn insert(&mut self, bounds: Rect<T>, value: V) -> SpatialHashHandle {
+        let cells = self.item_cells(&bounds);
+        let entry = Entry {
+            bounds,
+            value,
+            cells,
+        };
+        let index = if let Some(index) = self.free_entries.pop() {
+            self.entries[index] = Some(entry);
+            index
+        } else {
+            self.entries.push(Some(entry));
+            self.entries.len() - 1
+        };
+        self.len += 1;
+        self.link(index, cells);
+        SpatialHashHandle(index)
+    }
+
+    pub fn remove(&mut self, handle: SpatialHashHandle) -> Option<V> {
+        let entry = self.entries.get_mut(handle.0)?.take()?;
+        self.free_entries.push(handle.0);
+        self.len -= 1;
+        self.unlink(handle.0, entry.cells);
+        Some(entry.value)
+    }
+
+    /// Moves the item to `bounds`, returning `false` if there's no such item.
+    pub fn update(&mut self, handle: SpatialHashHandle, bounds: Rect<T>) -> bool {
+        let cells = self.item_cells(&bounds);
+        let previous = match self.entries.get_mut(handle.0).and_then(Option::as_mut) {
+            Some(entry) => {
+                entry.bounds = bounds;
+                std::mem::replace(&mut entry.cells, cells)
+            }
+            None => return false,
+        };
+        // Most moves stay within the same cells, and then there's nothing else to do.
+        if previous != cells {
+            self.unlink(handle.0, previous);
+            self.link(handle.0, cells);
+        }
+        true
+    }
+
+    /// Finds every item whose bounds overlap or touch `rect`.
+    pub fn query_rect(&self, rect: Rect<T>) -> impl Iterator<Item = (SpatialHashHandle, &V)> + '_ {
+        self.query(rect, move |bounds| bounds.intersects(&rect))
+    }
+
+    /// Finds every item whose bounds come within `radius` of `point`.
+    pub fn query_radius(
+        &self,
+        point: Point<T>,
+        radius: T,
+    ) -> impl Iterator<Item = (SpatialHashHandle, &V)> + '_ {
+        let area = Rect::from_points(point, point).outset_uniform(radius);
+        self.query(area, move |bounds| {
+            (bounds.closest_point(point) - point).magnitude_squared() <= radius * radius
+        })
+    }
+
+    /// Finds every pair of items whose bounds overlap or touch, which are the candidates for a more
+    /// precise collision test. Each pair is only reported once, with the smaller handle first.
+    pub fn candidate_pairs(
+        &self,
+    ) -> impl Iterator<Item = (SpatialHashHandle, SpatialHashHandle)> + '_ {
+        let gridded = self.cells.iter().flat_map(move |(cell, items)| {
+            items.iter().enumerate().flat_map(move |(position, a)| {
+                items[position + 1..].iter().filter_map(move |b| {
+                    let (a, b) = (self.entry_at(*a), self.entry_at(*b));
+                    // Items sharing several cells are only reported from the first one.
+                    let first =
+                        a.0.cells
+                            .zip(b.0.cells)
+                            .map(|((a, _), (b, _))| Point::new(a.x.max(b.x), a.y.max(b.y)));
+                    (first == Some(*cell) && a.0.bounds.intersects(&b.0.bounds))
+                        .then(|| ordered(a.1, b.1))
+                })
+            })
+        });
+        // Oversized items are checked against everything, and against each other only once.
+        let oversized = self.oversized.iter().flat_map(move |a| {
+            let a = self.entry_at(*a);
+            self.iter_entries().filter_map(move |b| {
+                let skip = b.1 == a.1 || (b.0.cells.is_none() && b.1 < a.1);
+                (!skip && a.0.bounds.intersects(&b.0.bounds)).then(|| ordered(a.1, b.1))
+            })
+        });
+        gridded.chain(oversized)
+    }
+
+    fn entry(&self, handle: SpatialHashHandle) -> Option<&Entry<V, T>> {
+        self.entries.get(handle.0).and_then(Option::as_ref)
+    }
+
+    fn entry_at(&self, index: usize) -> (&Entry<V, T>, SpatialHashHandle) {
+        (
+            self.entries[index].as_ref().unwrap(),
+            SpatialHashHandle(index),
+        )
+    }
+
+    fn iter_entries(&self) -> impl Iterator<Item = (&Entry<V, T>, SpatialHashHandle)> + '_ {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| Some((entry.as_ref()?, SpatialHashHandle(index))))
+    }
+
+    fn cell_range(&self, bounds: &Rect<T>) -> Option<CellRange> {
+        Some((
+            self.cell(bounds.top_left())?,
+            self.cell(bounds.bottom_right())?,
+        ))
+    }
+
+    fn item_cells(&self, bounds: &Rect<T>) -> Option<CellRange> {
+        self.cell_range(bounds)
+            .filter(|cells| cell_count(*cells) <= MAX_ITEM_CELLS)
+    }
+
+    fn cells_in((min, max): CellRange) -> impl Iterator<Item = Point<i64>> + Clone {
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+
+    fn link(&mut self, index: usize, cells: Option<CellRange>) {
+        match cells {
+            Some(cells) => {
+                for cell in Self::cells_in(cells) {
+                    self.cells.entry(cell).or_default().push(index);
+                }
+            }
+            None => self.oversized.push(index),
+        }
+    }
+
+    fn unlink(&mut self, index: usize, cells: Option<CellRange>) {
+        let cells = match cells {
+            Some(cells) => cells,
+            None => {
+                if let Some(position) = self.oversized.iter().position(|item| *item == index) {
+                    self.oversized.swap_remove(position);
+                }
+                return;
+            }
+        };
+        for cell in Self::cells_in(cells) {
+            if let Some(items) = self.cells.get_mut(&cell) {
+                if let Some(position) = items.iter().position(|item| *item == index) {
+                    items.swap_remove(position);
+                }
+                // Dropping empty cells keeps the map from growing as things move around.
+                if items.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+    }
+
+    /// Yields the items in the cells overlapping `area` whose bounds pass `test`.
+    fn query<'a>(
+        &'a self,
+        area: Rect<T>,
+        test: impl Fn(&Rect<T>) -> bool + Copy + 'a,
+    ) -> impl Iterator<Item = (SpatialHashHandle, &'a V)> + 'a {
+        let range = self.cell_range(&area);
+        // Walking a range with more cells than the whole grid holds would mostly turn up empty
+        // cells, so then it's cheaper to go through the occupied ones. That's also the only option
+        // when the range can't be numbered.
+        let walk = range.filter(|range| cell_count(*range) <= self.cells.len() as u128);
+        let walked = walk
+            .into_iter()
+            .flat_map(Self::cells_in)
+            .filter_map(move |cell| self.cells.get_key_value(&cell));
+        let scanned = walk
+            .is_none()
+            .then(|| {
+                self.cells.iter().filter(move |(cell, _)| match range {
+                    Some((min, max)) => {
+                        (min.x..=max.x).contains(&cell.x) && (min.y..=max.y).contains(&cell.y)
+                    }
+                    None => true,
+                })
+            })
+            .into_iter()
+            .flatten();
+        let gridded = walked.chain(scanned).flat_map(move |(cell, items)| {
+            items.iter().filter_map(move |index| {
+                let (entry, handle) = self.entry_at(*index);
+                // Items spanning several cells are only reported from the first one in range.
+                let first = entry.cells.map(|(first, _)| match range {
+                    Some((min, _)) => Point::new(min.x.max(first.x), min.y.max(first.y)),
+                    None => first,
+                });
+                (first == Some(*cell) && test(&entry.bounds)).then_some((handle, &entry.value))
+            })
+        });
+        let oversized = self.oversized.iter().filter_map(move |index| {
+            let (entry, handle) = self.entry_at(*index);
+            test(&entry.bounds).then_some((handle, &entry.value))
+        });
+        gridded.chain(oversized)
+    }
+}
+
+fn cell_count((min, max): CellRange) -> u128 {
+    let span = |min: i64, max: i64| (i128::from(max) - i128::from(min) + 1).max(0) as u128;
+    span(min.x, max.x).saturating_mul(span(min.y, max.y))
+}
+
+fn ordered(a: SpatialHashHandle, b: SpatialHashHandle) -> (SpatialHashHandle, SpatialHashHandle) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::{sorted, square};
+
+    #[test]
+    fn cells() {
+        let hash = SpatialHash::<(), f32>::new(10.0);
+        assert_eq!(hash.cell(Point::new(5.0, 15.0)), Some(Point::new(0, 1)));
+        assert_eq!(hash.cell(Point::new(-0.5, -10.0)), Some(Point::new(-1, -1)));
+        assert_eq!(hash.cell(Point::new(f32::NAN, 0.0)), None);
+        assert_eq!(hash.cell(Point::new(0.0, f32::INFINITY)), None);
+        assert_eq!(hash.cell_rect(Point::new(-1, 2)), square(-10.0, 20.0, 10.0));
+    }
+
+    #[test]
+    fn queries() {
+        let mut hash = SpatialHash::new(10.0);
+        let a = hash.insert(square(1.0, 1.0, 2.0), 0);
+        // This one spans four cells, but should only be reported once.
+        let b = hash.insert(square(8.0, 8.0, 4.0), 1);
+        let c = hash.insert(square(-30.0, 5.0, 1.0), 2);
+        assert_eq!(sorted(hash.query_rect(square(0.0, 0.0, 20.0))), vec![0, 1]);
+        assert_eq!(
+            sorted(hash.query_radius(Point::new(10.0, 10.0), 1.0)),
+            vec![1]
+        );
+        assert_eq!(
+            sorted(hash.query_radius(Point::new(-20.0, 5.0), 22.0)),
+            vec![0, 2]
+        );
+
+        assert!(hash.update(c, square(2.0, 2.0, 1.0)));
+        assert!(hash.update(b, square(9.0, 9.0, 4.0)));
+        assert_eq!(sorted(hash.query_rect(square(-30.0, 0.0, 10.0))), vec![]);
+        assert_eq!(hash.remove(a), Some(0));
+        assert_eq!(hash.remove(a), None);
+        assert_eq!(sorted(hash.query_rect(square(0.0, 0.0, 20.0))), vec![1, 2]);
+        assert_eq!(hash.len(), 2);
+    }
+
+    #[test]
+    fn pairs() {
+        let mut hash = SpatialHash::new(10.0);
+        let a = hash.insert(square(8.0, 8.0, 4.0), ());
+        let b = hash.insert(square(11.0, 11.0, 4.0), ());
+        let c = hash.insert(square(1.0, 1.0, 8.0), ());
+        hash.insert(square(30.0, 30.0, 1.0), ());
+        let mut pairs: Vec<_> = hash.candidate_pairs().collect();
+        pairs.sort_unstable();
+        assert_eq!(pairs, vec![(a, b), (a, c)]);
+    }
+
+    #[test]
+    fn oversized() {
+        let mut hash = SpatialHash::new(1.0);
+        let a = hash.insert(square(0.0, 0.0, 0.5), 0);
+        // These cover far too many cells to link, and the last can't be placed at all.
+        let b = hash.insert(square(-1e6, -1e6, 2e6), 1);
+        let c = hash.insert(square(-10.0, -10.0, 100.0), 2);
+        hash.insert(square(f32::NAN, 0.0, 1.0), 3);
+        let everything = Rect::from_top_right_bottom_left(
+            f32::NEG_INFINITY,
+            f32::INFINITY,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+        );
+        assert_eq!(sorted(hash.query_rect(everything)), vec![0, 1, 2]);
+        assert_eq!(
+            sorted(hash.query_rect(square(-1e9, -1e9, 2e9))),
+            vec![0, 1, 2]
+        );
+        assert_eq!(
+            sorted(hash.query_radius(Point::new(5.0, 5.0), 1.0)),
+            vec![1, 2]
+        );
+        assert_eq!(
+            sorted(hash.query_radius(Point::new(f32::NAN, 0.0), 1.0)),
+            vec![]
+        );
+        let mut pairs: Vec<_> = hash.candidate_pairs().collect();
+        pairs.sort_unstable();
+        assert_eq!(pairs, vec![(a, b), (a, c), (b, c)]);
+
+        assert!(hash.update(b, square(0.25, 0.25, 1.0)));
+        assert!(hash.update(a, square(-1e6, -1e6, 2e6)));
+        assert_eq!(
+            sorted(hash.query_rect(square(1.0, 1.0, 0.1))),
+            vec![0, 1, 2]
+        );
+        assert_eq!(hash.remove(c), Some(2));
+        let pairs: Vec<_> = hash.candidate_pairs().collect();
+        assert_eq!(pairs, vec![(a, b)]);
+    }
+}
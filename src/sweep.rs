@@ -0,0 +1,293 @@
+use crate::{Circle, Distance, LineSegment, Point, Rect, Vector};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Where and when a moving shape first hits another shape.
+///
+/// Sweeps take the velocity of the moving shape relative to the other shape, so if both shapes
+/// are moving, pass the difference between their velocities. Shapes that already overlap never
+/// hit, since they have no time of impact; `Collider::penetration` can separate them first.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Impact<T = f32> {
+    /// How far along its velocity the shape got before hitting, from 0 to 1.
+    pub time: T,
+    /// The unit normal of the surface that was hit, pointing back towards the moving shape.
+    pub normal: Vector<T>,
+    /// Where the shapes touch at the time of impact.
+    pub point: Point<T>,
+}
+
+/// Returns whether a hit is too close to a tangent to count. Without this, rounding error lets
+/// shapes snag on corners that they're only sliding past.
+fn grazing<T: en::Float>(discriminant: T, b: T) -> bool {
+    discriminant <= T::epsilon() * en::cast(16) * b * b
+}
+
+impl<T: en::Float> Rect<T> {
+    /// Finds when this rect hits `other` while moving by `velocity`. Rects that only slide along
+    /// each other's edges don't hit.
+    pub fn sweep_rect(&self, velocity: Vector<T>, other: &Rect<T>) -> Option<Impact<T>> {
+        // Returns the times that the rect enters and exits the other rect's span along one axis.
+        let span = |min: T, max: T, other_min: T, other_max: T, speed: T| {
+            if speed == T::zero() {
+                if min < other_max && max > other_min {
+                    Some((T::neg_infinity(), T::infinity()))
+                } else {
+                    None
+                }
+            } else if speed > T::zero() {
+                Some(((other_min - max) / speed, (other_max - min) / speed))
+            } else {
+                Some(((other_max - min) / speed, (other_min - max) / speed))
+            }
+        };
+        let (enter_x, exit_x) = span(
+            self.left(),
+            self.right(),
+            other.left(),
+            other.right(),
+            velocity.dx,
+        )?;
+        let (enter_y, exit_y) = span(
+            self.top(),
+            self.bottom(),
+            other.top(),
+            other.bottom(),
+            velocity.dy,
+        )?;
+        let (time, horizontal) = if enter_x >= enter_y {
+            (enter_x, true)
+        } else {
+            (enter_y, false)
+        };
+        let exit = en::Num::min(exit_x, exit_y);
+        if time < T::zero() || time > T::one() || time >= exit {
+            return None;
+        }
+        let moved = self.translate(velocity * time);
+        // The contact point is in the middle of where the touching edges overlap.
+        let middle = |min: T, max: T, other_min: T, other_max: T| {
+            (en::Num::max(min, other_min) + en::Num::min(max, other_max)).halved()
+        };
+        let (normal, point) = if horizontal {
+            let (normal, x) = if velocity.dx > T::zero() {
+                (Vector::from_dx(-T::one()), other.left())
+            } else {
+                (Vector::from_dx(T::one()), other.right())
+            };
+            let y = middle(moved.top(), moved.bottom(), other.top(), other.bottom());
+            (normal, Point::new(x, y))
+        } else {
+            let (normal, y) = if velocity.dy > T::zero() {
+                (Vector::from_dy(-T::one()), other.top())
+            } else {
+                (Vector::from_dy(T::one()), other.bottom())
+            };
+            let x = middle(moved.left(), moved.right(), other.left(), other.right());
+            (normal, Point::new(x, y))
+        };
+        Some(Impact {
+            time,
+            normal,
+            point,
+        })
+    }
+}
+
+impl<T: en::Float> Circle<T> {
+    /// Finds when this circle hits `other` while moving by `velocity`.
+    pub fn sweep_circle(&self, velocity: Vector<T>, other: &Circle<T>) -> Option<Impact<T>> {
+        let radius = self.radius() + other.radius();
+        let offset = self.center() - other.center();
+        // Solve for when the distance between the centers is the sum of the radii.
+        let a = velocity.magnitude_squared();
+        let b = T::two() * offset.dot_product(velocity);
+        let c = offset.magnitude_squared() - radius * radius;
+        // The circles are already overlapping, or aren't approaching each other.
+        if c < T::zero() || b >= T::zero() {
+            return None;
+        }
+        let discriminant = b * b - en::cast::<T, _>(4) * a * c;
+        if discriminant < T::zero() || grazing(discriminant, b) {
+            return None;
+        }
+        let time = (-b - discriminant.sqrt()) / (T::two() * a);
+        if time > T::one() {
+            return None;
+        }
+        let normal = (offset + velocity * time).normalize();
+        Some(Impact {
+            time,
+            normal,
+            point: other.center() + normal * other.radius(),
+        })
+    }
+
+    /// Finds when this circle hits `segment` while moving by `velocity`.
+    pub fn sweep_line_segment(
+        &self,
+        velocity: Vector<T>,
+        segment: &LineSegment<T>,
+    ) -> Option<Impact<T>> {
+        let radius = self.radius();
+        let nearest = segment.closest_point(self.center());
+        if (self.center() - nearest).magnitude_squared() < radius * radius {
+            return None;
+        }
+        let vector = segment.vector();
+        let length_squared = vector.magnitude_squared();
+        let mut side = None;
+        if length_squared != T::zero() {
+            // Check the flat side that faces the circle.
+            let mut normal = Vector::new(vector.dy, -vector.dx).normalize();
+            let mut distance = (self.center() - segment.from).dot_product(normal);
+            if distance < T::zero() {
+                normal = -normal;
+                distance = -distance;
+            }
+            let speed = -velocity.dot_product(normal);
+            if distance >= radius && speed > T::zero() {
+                let time = (distance - radius) / speed;
+                let point = self.center() + velocity * time - normal * radius;
+                let along = (point - segment.from).dot_product(vector) / length_squared;
+                if time <= T::one() && along >= T::zero() && along <= T::one() {
+                    side = Some(Impact {
+                        time,
+                        normal,
+                        point,
+                    });
+                }
+            }
+        }
+        // Otherwise, the circle can only hit one of the ends.
+        side.or_else(|| {
+            segment
+                .points()
+                .filter_map(|point| self.sweep_circle(velocity, &Circle::new(point, T::zero())))
+                .fold(None, earliest)
+        })
+    }
+
+    /// Finds when this circle hits `rect` while moving by `velocity`.
+    pub fn sweep_rect(&self, velocity: Vector<T>, rect: &Rect<T>) -> Option<Impact<T>> {
+        // Shapes that already overlap never hit, and otherwise the circle has to cross one of the
+        // rect's edges to get in.
+        let radius = self.radius();
+        if (rect.closest_point(self.center()) - self.center()).magnitude_squared() < radius * radius
+        {
+            return None;
+        }
+        rect.line_segments()
+            .iter()
+            .filter_map(|segment| self.sweep_line_segment(velocity, segment))
+            .fold(None, earliest)
+    }
+}
+
+fn earliest<T: en::Float>(earliest: Option<Impact<T>>, impact: Impact<T>) -> Option<Impact<T>> {
+    match earliest {
+        Some(earliest) if earliest.time <= impact.time => Some(earliest),
+        _ => Some(impact),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, test::rect};
+
+    #[test]
+    fn rects() {
+        // A fast bullet would skip right over this wall if it were only checked each frame.
+        let bullet = rect(0.0, 0.0, 1.0, 1.0);
+        let wall = rect(10.0, -5.0, 0.5, 10.0);
+        let impact = bullet.sweep_rect(Vector::new(40.0, 0.0), &wall).unwrap();
+        assert_approx_eq!(impact.time, 9.0 / 40.0);
+        assert_eq!(impact.normal, Vector::new(-1.0, 0.0));
+        assert_eq!(impact.point, Point::new(10.0, 0.5));
+        assert!(bullet.sweep_rect(Vector::new(-40.0, 0.0), &wall).is_none());
+        assert!(bullet.sweep_rect(Vector::new(5.0, 0.0), &wall).is_none());
+
+        // Sliding along the floor doesn't hit it, but landing on it does.
+        let floor = rect(-10.0, 1.0, 20.0, 1.0);
+        assert!(bullet.sweep_rect(Vector::new(3.0, 0.0), &floor).is_none());
+        let impact = bullet
+            .translate(Vector::new(0.0, -2.0))
+            .sweep_rect(Vector::new(1.0, 4.0), &floor)
+            .unwrap();
+        assert_approx_eq!(impact.time, 0.5);
+        assert_eq!(impact.normal, Vector::new(0.0, -1.0));
+        // Overlapping rects are ignored.
+        assert!(bullet
+            .sweep_rect(Vector::new(1.0, 0.0), &rect(0.5, 0.5, 1.0, 1.0))
+            .is_none());
+    }
+
+    #[test]
+    fn circles() {
+        let circle = Circle::new(Point::zero(), 1.0);
+        let other = Circle::new(Point::new(10.0, 0.0), 2.0);
+        let impact = circle.sweep_circle(Vector::new(14.0, 0.0), &other).unwrap();
+        assert_approx_eq!(impact.time, 0.5);
+        assert_eq!(impact.normal, Vector::new(-1.0, 0.0));
+        assert_eq!(impact.point, Point::new(8.0, 0.0));
+        assert!(circle
+            .sweep_circle(Vector::new(14.0, 6.0), &other)
+            .is_none());
+        // Brushing right past the other circle doesn't count.
+        let brush = Circle::new(Point::new(0.0, 3.0), 1.0);
+        assert!(brush.sweep_circle(Vector::new(20.0, 0.0), &other).is_none());
+    }
+
+    #[test]
+    fn line_segments() {
+        let segment = LineSegment::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+        let circle = Circle::new(Point::new(1.0, -3.0), 1.0);
+        let impact = circle
+            .sweep_line_segment(Vector::new(0.0, 4.0), &segment)
+            .unwrap();
+        assert_approx_eq!(impact.time, 0.5);
+        assert_eq!(impact.normal, Vector::new(0.0, -1.0));
+        assert_eq!(impact.point, Point::new(1.0, 0.0));
+
+        // This circle misses the side and catches the end.
+        let circle = Circle::new(Point::new(-0.6, -3.0), 1.0);
+        let impact = circle
+            .sweep_line_segment(Vector::new(0.0, 4.0), &segment)
+            .unwrap();
+        assert_approx_eq!(impact.time, 0.55);
+        assert_eq!(impact.point, Point::new(0.0, 0.0));
+        assert_approx_eq!(impact.normal.dx, -0.6);
+        assert_approx_eq!(impact.normal.dy, -0.8);
+    }
+
+    #[test]
+    fn circle_rect() {
+        let block = rect(0.0, 0.0, 4.0, 4.0);
+        let circle = Circle::new(Point::new(-3.0, 2.0), 1.0);
+        let impact = circle.sweep_rect(Vector::new(4.0, 0.0), &block).unwrap();
+        assert_approx_eq!(impact.time, 0.5);
+        assert_eq!(impact.normal, Vector::new(-1.0, 0.0));
+
+        // Coming in diagonally, the circle hits the corner.
+        let circle = Circle::new(Point::new(-2.0, -2.0), 1.0);
+        let impact = circle.sweep_rect(Vector::new(4.0, 4.0), &block).unwrap();
+        assert_eq!(impact.point, Point::zero());
+        assert_approx_eq!(impact.time, (2.0 - 0.5f32.sqrt()) / 4.0);
+
+        // Rolling along the top doesn't snag on the far corner.
+        let circle = Circle::new(Point::new(1.0, -1.0), 1.0);
+        assert!(circle.sweep_rect(Vector::new(8.0, 0.0), &block).is_none());
+
+        // Starting inside the rect, or overlapping one of its edges, doesn't count as hitting it.
+        let room = rect(0.0, 0.0, 10.0, 10.0);
+        let inside = Circle::new(Point::new(5.0, 5.0), 1.0);
+        assert!(inside.sweep_rect(Vector::new(10.0, 0.0), &room).is_none());
+        let overlapping = Circle::new(Point::new(0.5, 5.0), 1.0);
+        assert!(overlapping
+            .sweep_rect(Vector::new(20.0, 0.0), &room)
+            .is_none());
+    }
+}
@@ -0,0 +1,660 @@
+use crate::{orient2d, Point, Polygon, PolygonWithHoles, Rect};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A triangle mesh, ready to be uploaded as a vertex buffer and an index buffer.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Triangulation<T = f32> {
+    pub points: Vec<Point<T>>,
+    pub indices: Vec<[u32; 3]>,
+}
+
+impl<T: en::Num> Triangulation<T> {
+    pub fn triangles(&self) -> impl Iterator<Item = [Point<T>; 3]> + Clone + '_ {
+        self.indices
+            .iter()
+            .map(move |triangle| triangle.map(|index| self.points[index as usize]))
+    }
+
+    pub fn bounding_rect(&self) -> Rect<T> {
+        Rect::from_iter(&self.points)
+    }
+
+    pub fn map<U: en::Num>(self, f: impl FnMut(Point<T>) -> Point<U>) -> Triangulation<U> {
+        Triangulation {
+            points: self.points.into_iter().map(f).collect(),
+            indices: self.indices,
+        }
+    }
+
+    pub fn cast<U: en::Num>(self) -> Triangulation<U> {
+        self.map(Point::cast)
+    }
+
+    impl_casts!(Triangulation);
+}
+
+impl<T: en::Float> Triangulation<T> {
+    pub fn area(&self) -> T {
+        self.triangles().fold(T::zero(), |sum, [a, b, c]| {
+            sum + (b - a).cross_product(c - a).abs().halved()
+        })
+    }
+}
+
+impl<T: en::Float> Polygon<T> {
+    /// Splits the polygon into triangles by ear clipping. The triangles index into the polygon's
+    /// points and are wound clockwise.
+    pub fn triangulate(&self) -> Triangulation<T> {
+        triangulate(self, &[])
+    }
+}
+
+impl<T: en::Float> PolygonWithHoles<T> {
+    /// Splits the polygon into triangles by ear clipping, after bridging each hole into the outer
+    /// ring. The triangles index into the outer ring's points followed by each hole's points, and
+    /// are wound clockwise.
+    pub fn triangulate(&self) -> Triangulation<T> {
+        triangulate(&self.outer, &self.holes)
+    }
+}
+
+// This is a port of the core of mapbox's earcut (https://github.com/mapbox/earcut), minus the
+// z-order curve hashing. Earcut is ISC licensed, Copyright (c) 2016, Mapbox; see
+// THIRD_PARTY_NOTICES.md for the full notice.
+fn triangulate<T: en::Float>(outer: &Polygon<T>, holes: &[Polygon<T>]) -> Triangulation<T> {
+    let points: Vec<_> = std::iter::once(outer)
+        .chain(holes)
+        .flat_map(|ring| ring.points().iter().copied())
+        .collect();
+    let mut indices = Vec::new();
+    let mut list = RingList::default();
+    if let Some(outer_node) = list.push_ring(outer, 0, true) {
+        if list.next(outer_node) != list.prev(outer_node) {
+            let outer_node = list.eliminate_holes(holes, outer.len(), outer_node);
+            list.earcut(Some(outer_node), &mut indices, Pass::Initial);
+        }
+    }
+    Triangulation { points, indices }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Pass {
+    Initial,
+    Filtered,
+    Cured,
+}
+
+#[derive(Debug)]
+struct Node<T> {
+    index: u32,
+    point: Point<T>,
+    prev: usize,
+    next: usize,
+}
+
+#[derive(Debug)]
+struct RingList<T> {
+    nodes: Vec<Node<T>>,
+}
+
+impl<T> Default for RingList<T> {
+    fn default() -> Self {
+        Self { nodes: Vec::new() }
+    }
+}
+
+/// Twice the signed area of the triangle `pqr`, which is negative when `pqr` turns clockwise.
+fn area<T: en::Float>(p: Point<T>, q: Point<T>, r: Point<T>) -> f64 {
+    -orient2d(p, q, r)
+}
+
+fn point_in_triangle<T: en::Float>(a: Point<T>, b: Point<T>, c: Point<T>, p: Point<T>) -> bool {
+    orient2d(p, c, a) >= 0.0 && orient2d(p, a, b) >= 0.0 && orient2d(p, b, c) >= 0.0
+}
+
+fn sign<T: en::Float>(value: T) -> i8 {
+    if value > T::zero() {
+        1
+    } else if value < T::zero() {
+        -1
+    } else {
+        0
+    }
+}
+
+/// Assuming `p`, `q`, and `r` are collinear, checks if `q` lies on the segment `pr`.
+fn on_segment<T: en::Float>(p: Point<T>, q: Point<T>, r: Point<T>) -> bool {
+    Rect::from_points(p, r).contains_inclusive(q)
+}
+
+fn segments_intersect<T: en::Float>(
+    p1: Point<T>,
+    q1: Point<T>,
+    p2: Point<T>,
+    q2: Point<T>,
+) -> bool {
+    let o1 = sign(area(p1, q1, p2));
+    let o2 = sign(area(p1, q1, q2));
+    let o3 = sign(area(p2, q2, p1));
+    let o4 = sign(area(p2, q2, q1));
+    (o1 != o2 && o3 != o4)
+        || (o1 == 0 && on_segment(p1, p2, q1))
+        || (o2 == 0 && on_segment(p1, q2, q1))
+        || (o3 == 0 && on_segment(p2, p1, q2))
+        || (o4 == 0 && on_segment(p2, q1, q2))
+}
+
+impl<T: en::Float> RingList<T> {
+    fn point(&self, node: usize) -> Point<T> {
+        self.nodes[node].point
+    }
+
+    fn index(&self, node: usize) -> u32 {
+        self.nodes[node].index
+    }
+
+    fn prev(&self, node: usize) -> usize {
+        self.nodes[node].prev
+    }
+
+    fn next(&self, node: usize) -> usize {
+        self.nodes[node].next
+    }
+
+    fn insert(&mut self, index: u32, point: Point<T>, last: Option<usize>) -> usize {
+        let node = self.nodes.len();
+        match last {
+            None => self.nodes.push(Node {
+                index,
+                point,
+                prev: node,
+                next: node,
+            }),
+            Some(last) => {
+                let next = self.next(last);
+                self.nodes.push(Node {
+                    index,
+                    point,
+                    prev: last,
+                    next,
+                });
+                self.nodes[next].prev = node;
+                self.nodes[last].next = node;
+            }
+        }
+        node
+    }
+
+    fn remove(&mut self, node: usize) {
+        let Node { prev, next, .. } = self.nodes[node];
+        self.nodes[next].prev = prev;
+        self.nodes[prev].next = next;
+    }
+
+    /// Links up a ring, wound clockwise if `clockwise` is set and counter-clockwise otherwise.
+    fn push_ring(&mut self, ring: &Polygon<T>, offset: usize, clockwise: bool) -> Option<usize> {
+        let mut last = None;
+        let mut push = |list: &mut Self, (i, point): (usize, &Point<T>)| {
+            last = Some(list.insert((offset + i) as u32, *point, last));
+        };
+        if clockwise == (ring.signed_area() > T::zero()) {
+            ring.points().iter().enumerate().for_each(|p| push(self, p));
+        } else {
+            ring.points()
+                .iter()
+                .enumerate()
+                .rev()
+                .for_each(|p| push(self, p));
+        }
+        let last = last?;
+        if self.point(last) == self.point(self.next(last)) {
+            self.remove(last);
+            return Some(self.next(last));
+        }
+        Some(last)
+    }
+
+    /// Removes duplicate and collinear points between `start` and `end`.
+    fn filter_points(&mut self, start: usize, end: Option<usize>) -> usize {
+        let mut end = end.unwrap_or(start);
+        let mut p = start;
+        loop {
+            let mut again = false;
+            let (prev, next) = (self.prev(p), self.next(p));
+            if self.point(p) == self.point(next)
+                || area(self.point(prev), self.point(p), self.point(next)) == 0.0
+            {
+                self.remove(p);
+                p = prev;
+                end = prev;
+                if p == self.next(p) {
+                    break;
+                }
+                again = true;
+            } else {
+                p = next;
+            }
+            if !again && p == end {
+                break;
+            }
+        }
+        end
+    }
+
+    fn earcut(&mut self, ear: Option<usize>, indices: &mut Vec<[u32; 3]>, pass: Pass) {
+        let mut ear = match ear {
+            Some(ear) => ear,
+            None => return,
+        };
+        let mut stop = ear;
+        while self.prev(ear) != self.next(ear) {
+            let (prev, next) = (self.prev(ear), self.next(ear));
+            if self.is_ear(ear) {
+                indices.push([self.index(prev), self.index(ear), self.index(next)]);
+                self.remove(ear);
+                ear = self.next(next);
+                stop = ear;
+                continue;
+            }
+            ear = next;
+            if ear == stop {
+                // We went all the way around without finding an ear, so try progressively more
+                // forceful approaches.
+                match pass {
+                    Pass::Initial => {
+                        let ear = self.filter_points(ear, None);
+                        self.earcut(Some(ear), indices, Pass::Filtered);
+                    }
+                    Pass::Filtered => {
+                        let ear = self.filter_points(ear, None);
+                        let ear = self.cure_local_intersections(ear, indices);
+                        self.earcut(Some(ear), indices, Pass::Cured);
+                    }
+                    Pass::Cured => self.split_earcut(ear, indices),
+                }
+                break;
+            }
+        }
+    }
+
+    fn is_ear(&self, ear: usize) -> bool {
+        let (a, b, c) = (
+            self.point(self.prev(ear)),
+            self.point(ear),
+            self.point(self.next(ear)),
+        );
+        if area(a, b, c) >= 0.0 {
+            // Reflex vertices can't be ears.
+            return false;
+        }
+        let bounds = Rect::from_iter([a, b, c]);
+        let mut p = self.next(self.next(ear));
+        while p != self.prev(ear) {
+            let point = self.point(p);
+            if bounds.contains_inclusive(point)
+                && point_in_triangle(a, b, c, point)
+                && area(self.point(self.prev(p)), point, self.point(self.next(p))) >= 0.0
+            {
+                return false;
+            }
+            p = self.next(p);
+        }
+        true
+    }
+
+    /// Clips off the triangles formed by small self-intersections.
+    fn cure_local_intersections(&mut self, start: usize, indices: &mut Vec<[u32; 3]>) -> usize {
+        let mut start = start;
+        let mut p = start;
+        loop {
+            let a = self.prev(p);
+            let b = self.next(self.next(p));
+            if self.point(a) != self.point(b)
+                && segments_intersect(
+                    self.point(a),
+                    self.point(p),
+                    self.point(self.next(p)),
+                    self.point(b),
+                )
+                && self.locally_inside(a, b)
+                && self.locally_inside(b, a)
+            {
+                indices.push([self.index(a), self.index(p), self.index(b)]);
+                let next = self.next(p);
+                self.remove(p);
+                self.remove(next);
+                p = b;
+                start = b;
+            }
+            p = self.next(p);
+            if p == start {
+                break;
+            }
+        }
+        self.filter_points(p, None)
+    }
+
+    /// Splits the ring along a valid diagonal and triangulates both halves separately.
+    fn split_earcut(&mut self, start: usize, indices: &mut Vec<[u32; 3]>) {
+        let mut a = start;
+        loop {
+            let mut b = self.next(self.next(a));
+            while b != self.prev(a) {
+                if self.index(a) != self.index(b) && self.is_valid_diagonal(a, b) {
+                    let c = self.split(a, b);
+                    let a = self.filter_points(a, Some(self.next(a)));
+                    let c = self.filter_points(c, Some(self.next(c)));
+                    self.earcut(Some(a), indices, Pass::Initial);
+                    self.earcut(Some(c), indices, Pass::Initial);
+                    return;
+                }
+                b = self.next(b);
+            }
+            a = self.next(a);
+            if a == start {
+                break;
+            }
+        }
+    }
+
+    fn eliminate_holes(&mut self, holes: &[Polygon<T>], offset: usize, outer: usize) -> usize {
+        let mut offset = offset;
+        let mut queue = Vec::with_capacity(holes.len());
+        for hole in holes {
+            if hole.len() >= 3 {
+                if let Some(node) = self.push_ring(hole, offset, false) {
+                    queue.push(self.leftmost(node));
+                }
+            }
+            offset += hole.len();
+        }
+        queue.sort_by(|a, b| {
+            let (a, b) = (self.point(*a), self.point(*b));
+            a.x.partial_cmp(&b.x)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.y.partial_cmp(&b.y).unwrap_or(std::cmp::Ordering::Equal))
+        });
+        queue
+            .into_iter()
+            .fold(outer, |outer, hole| self.eliminate_hole(hole, outer))
+    }
+
+    fn eliminate_hole(&mut self, hole: usize, outer: usize) -> usize {
+        match self.find_hole_bridge(hole, outer) {
+            Some(bridge) => {
+                let bridge_reverse = self.split(bridge, hole);
+                self.filter_points(bridge_reverse, Some(self.next(bridge_reverse)));
+                self.filter_points(bridge, Some(self.next(bridge)))
+            }
+            None => outer,
+        }
+    }
+
+    /// Finds a vertex of the outer ring that can be connected to `hole` without crossing any edges.
+    fn find_hole_bridge(&self, hole: usize, outer: usize) -> Option<usize> {
+        let h = self.point(hole);
+        let mut qx = T::neg_infinity();
+        let mut m = None;
+
+        // Find the closest edge to the left of the hole's leftmost point; its leftmost endpoint is
+        // a candidate for the bridge.
+        let mut p = outer;
+        loop {
+            let (a, b) = (self.point(p), self.point(self.next(p)));
+            if h.y <= a.y && h.y >= b.y && b.y != a.y {
+                let x = a.x + (h.y - a.y) * (b.x - a.x) / (b.y - a.y);
+                if x <= h.x && x > qx {
+                    qx = x;
+                    let candidate = if a.x < b.x { p } else { self.next(p) };
+                    if x == h.x {
+                        // The hole touches the outer ring here.
+                        return Some(candidate);
+                    }
+                    m = Some(candidate);
+                }
+            }
+            p = self.next(p);
+            if p == outer {
+                break;
+            }
+        }
+        let mut m = m?;
+
+        // If any vertices lie within the triangle between the hole point, the ray hit, and the
+        // candidate, they'd block the bridge, so choose whichever is closest in angle to the ray.
+        let stop = m;
+        let mp = self.point(m);
+        let mut tan_min = T::infinity();
+        let mut p = m;
+        loop {
+            let point = self.point(p);
+            let (a, c) = if h.y < mp.y {
+                (Point::new(h.x, h.y), Point::new(qx, h.y))
+            } else {
+                (Point::new(qx, h.y), Point::new(h.x, h.y))
+            };
+            if h.x >= point.x
+                && point.x >= mp.x
+                && h.x != point.x
+                && point_in_triangle(a, mp, c, point)
+            {
+                let tan = (h.y - point.y).abs() / (h.x - point.x);
+                let m_point = self.point(m);
+                if self.locally_inside(p, hole)
+                    && (tan < tan_min
+                        || (tan == tan_min
+                            && (point.x > m_point.x
+                                || (point.x == m_point.x && self.sector_contains_sector(m, p)))))
+                {
+                    m = p;
+                    tan_min = tan;
+                }
+            }
+            p = self.next(p);
+            if p == stop {
+                break;
+            }
+        }
+        Some(m)
+    }
+
+    fn sector_contains_sector(&self, m: usize, p: usize) -> bool {
+        area(
+            self.point(self.prev(m)),
+            self.point(m),
+            self.point(self.prev(p)),
+        ) < 0.0
+            && area(
+                self.point(self.next(p)),
+                self.point(m),
+                self.point(self.next(m)),
+            ) < 0.0
+    }
+
+    fn leftmost(&self, start: usize) -> usize {
+        let mut p = start;
+        let mut leftmost = start;
+        loop {
+            let (point, best) = (self.point(p), self.point(leftmost));
+            if point.x < best.x || (point.x == best.x && point.y < best.y) {
+                leftmost = p;
+            }
+            p = self.next(p);
+            if p == start {
+                break;
+            }
+        }
+        leftmost
+    }
+
+    fn is_valid_diagonal(&self, a: usize, b: usize) -> bool {
+        let (pa, pb) = (self.point(a), self.point(b));
+        let (a_prev, a_next) = (self.point(self.prev(a)), self.point(self.next(a)));
+        let (b_prev, b_next) = (self.point(self.prev(b)), self.point(self.next(b)));
+        self.index(self.next(a)) != self.index(b)
+            && self.index(self.prev(a)) != self.index(b)
+            && !self.intersects_ring(a, b)
+            && ((self.locally_inside(a, b)
+                && self.locally_inside(b, a)
+                && self.middle_inside(a, b)
+                && (area(a_prev, pa, b_prev) != 0.0 || area(pa, b_prev, pb) != 0.0))
+                || (pa == pb && area(a_prev, pa, a_next) > 0.0 && area(b_prev, pb, b_next) > 0.0))
+    }
+
+    fn intersects_ring(&self, a: usize, b: usize) -> bool {
+        let (ia, ib) = (self.index(a), self.index(b));
+        let mut p = a;
+        loop {
+            let next = self.next(p);
+            let (ip, inext) = (self.index(p), self.index(next));
+            if ip != ia
+                && inext != ia
+                && ip != ib
+                && inext != ib
+                && segments_intersect(
+                    self.point(p),
+                    self.point(next),
+                    self.point(a),
+                    self.point(b),
+                )
+            {
+                return true;
+            }
+            p = next;
+            if p == a {
+                break;
+            }
+        }
+        false
+    }
+
+    /// Checks if the diagonal from `a` to `b` starts off inside the ring.
+    fn locally_inside(&self, a: usize, b: usize) -> bool {
+        let (prev, pa, next, pb) = (
+            self.point(self.prev(a)),
+            self.point(a),
+            self.point(self.next(a)),
+            self.point(b),
+        );
+        if area(prev, pa, next) < 0.0 {
+            area(pa, pb, next) >= 0.0 && area(pa, prev, pb) >= 0.0
+        } else {
+            area(pa, pb, prev) < 0.0 || area(pa, next, pb) < 0.0
+        }
+    }
+
+    /// Checks if the midpoint of the diagonal from `a` to `b` is inside the ring.
+    fn middle_inside(&self, a: usize, b: usize) -> bool {
+        let mid = Point::new(
+            (self.point(a).x + self.point(b).x).halved(),
+            (self.point(a).y + self.point(b).y).halved(),
+        );
+        let mut inside = false;
+        let mut p = a;
+        loop {
+            let (from, to) = (self.point(p), self.point(self.next(p)));
+            if (from.y > mid.y) != (to.y > mid.y)
+                && to.y != from.y
+                && mid.x < (to.x - from.x) * (mid.y - from.y) / (to.y - from.y) + from.x
+            {
+                inside = !inside;
+            }
+            p = self.next(p);
+            if p == a {
+                break;
+            }
+        }
+        inside
+    }
+
+    /// Connects `a` and `b` with a diagonal, splitting the ring in two. Both `a` and `b` are
+    /// duplicated so that each ring gets its own copy; the returned node is `b`'s copy.
+    fn split(&mut self, a: usize, b: usize) -> usize {
+        let a2 = self.nodes.len();
+        let b2 = a2 + 1;
+        let (an, bp) = (self.next(a), self.prev(b));
+        self.nodes.push(Node {
+            index: self.index(a),
+            point: self.point(a),
+            prev: b2,
+            next: an,
+        });
+        self.nodes.push(Node {
+            index: self.index(b),
+            point: self.point(b),
+            prev: bp,
+            next: a2,
+        });
+        self.nodes[a].next = b;
+        self.nodes[b].prev = a;
+        self.nodes[an].prev = a2;
+        self.nodes[bp].next = b2;
+        b2
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{assert_approx_eq, Size};
+
+    fn check(triangulation: &Triangulation, expected_area: f32, expected_triangles: usize) {
+        assert_eq!(triangulation.indices.len(), expected_triangles);
+        assert_approx_eq!(triangulation.area(), expected_area);
+        for [a, b, c] in triangulation.triangles() {
+            assert!(
+                (b - a).cross_product(c - a) > 0.0,
+                "triangle `{:?}` isn't wound clockwise",
+                [a, b, c]
+            );
+        }
+    }
+
+    #[test]
+    fn convex() {
+        let square = Polygon::from(Rect::from_top_left(Point::zero(), Size::square(2.0)));
+        check(&square.triangulate(), 4.0, 2);
+        check(&square.reverse().triangulate(), 4.0, 2);
+    }
+
+    #[test]
+    fn concave() {
+        let comb = Polygon::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 2.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 0.0),
+            Point::new(3.0, 0.0),
+            Point::new(3.0, 3.0),
+            Point::new(0.0, 3.0),
+        ]);
+        check(&comb.triangulate(), 7.0, 6);
+    }
+
+    #[test]
+    fn holes() {
+        let outer = Polygon::from(Rect::from_top_left(Point::zero(), Size::square(10.0)));
+        let hole_a = Polygon::from(Rect::from_top_left(Point::new(2.0, 2.0), Size::square(2.0)));
+        let hole_b = Polygon::from(Rect::from_top_left(Point::new(6.0, 5.0), Size::square(3.0)));
+        let polygon = PolygonWithHoles::new(outer, vec![hole_a, hole_b]);
+        let triangulation = polygon.triangulate();
+        assert_eq!(triangulation.points.len(), 12);
+        // A polygon with `n` points and `h` holes always has `n + 2h - 2` triangles.
+        check(&triangulation, 100.0 - 4.0 - 9.0, 14);
+    }
+
+    #[test]
+    fn degenerate() {
+        let line = Polygon::new(vec![Point::new(0.0, 0.0), Point::new(1.0, 1.0)]);
+        assert!(line.triangulate().indices.is_empty());
+        let collinear = Polygon::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 0.0),
+        ]);
+        assert!(collinear.triangulate().indices.is_empty());
+    }
+}
@@ -58,6 +58,13 @@ impl<T: en::Num> Vector<T> {
         self.dx * rhs.dx + self.dy * rhs.dy
     }
 
+    /// Returns the z component of the 3D cross product. This is positive when `rhs` points clockwise of
+    /// `self` (as seen on screen), negative when it points counter-clockwise, and zero when they're
+    /// parallel.
+    pub fn cross_product(self, rhs: Self) -> T {
+        self.dx * rhs.dy - self.dy * rhs.dx
+    }
+
     pub fn magnitude_squared(self) -> T {
         self.dot_product(self)
     }